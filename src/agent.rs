@@ -0,0 +1,130 @@
+//! Players that choose their own actions.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::{take_turn, Action, Board, PlayerView, TurnResult};
+
+mod greedy;
+mod ismcts;
+
+pub use greedy::{GreedyAgent, GreedyStyle};
+pub use ismcts::{IsmctsAgent, SearchLimits};
+
+/// A player that chooses its own actions from what it can see of the board.
+///
+/// An agent is asked for an action whenever it is the next player. After a
+/// `StartNegotiation` the turn does not pass, so the agent is asked again
+/// with a view that [is negotiating](crate::SpectatorView::is_negotiating),
+/// and must answer with an `EndNegotiation`.
+///
+/// The agents here draw their random choices from a generator they are given,
+/// so the same generator state always makes the same choices.
+pub trait Agent {
+    /// Chooses the action the viewer takes next,
+    /// or returns `None` if no action is legal, as once the game is over.
+    ///
+    /// The view always belongs to the next player.
+    fn choose_action(&mut self, view: &PlayerView) -> Option<Action>;
+}
+
+/// An agent that picks uniformly from the legal actions.
+#[derive(Clone, Debug)]
+pub struct RandomAgent<R> {
+    rng: R,
+}
+
+impl<R: Rng> RandomAgent<R> {
+    /// Creates an agent that draws its choices from `rng`.
+    pub const fn new(rng: R) -> Self {
+        Self { rng }
+    }
+}
+
+impl<R: Rng> Agent for RandomAgent<R> {
+    fn choose_action(&mut self, view: &PlayerView) -> Option<Action> {
+        view.legal_actions().choose(&mut self.rng).copied()
+    }
+}
+
+/// Takes the next turn with the action the agent chooses for the next player.
+///
+/// # Returns
+///
+/// [`TurnResult`] - As returned by [`take_turn`],
+/// or `None` if the agent has no action to take, as once the game is over.
+#[must_use]
+pub fn take_agent_turn<A: Agent + ?Sized>(board: Board, agent: &mut A) -> Option<TurnResult> {
+    let action = agent.choose_action(&board.view_for(board.next_player()))?;
+    Some(take_turn(board, action))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{start_game_with_seed, Winner};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// Plays a game between random agents, returning every board and the winner.
+    fn play(num_players: u8, seed: u64) -> (Vec<Board>, Winner) {
+        let mut agent = RandomAgent::new(StdRng::seed_from_u64(seed));
+        let mut boards = vec![start_game_with_seed(num_players, seed).unwrap()];
+        loop {
+            let TurnResult(board, result) =
+                take_agent_turn(boards[boards.len() - 1], &mut agent).unwrap();
+            boards.push(board);
+            if let Some(winner) = result.unwrap() {
+                return (boards, winner);
+            }
+        }
+    }
+
+    #[test]
+    fn plays_games_to_the_end() {
+        for num_players in 2..=5 {
+            let (boards, _) = play(num_players, 11);
+            let last = boards[boards.len() - 1];
+            assert!(last.is_over());
+            assert_eq!(last.check_invariants(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn ends_negotiations_it_starts() {
+        let board = start_game_with_seed(3, 4).unwrap();
+        let TurnResult(board, _) = take_turn(board, Action::StartNegotiation);
+        let mut agent = RandomAgent::new(StdRng::seed_from_u64(4));
+        let action = agent.choose_action(&board.view_for(board.next_player()));
+        assert!(matches!(action, Some(Action::EndNegotiation(_))));
+    }
+
+    #[test]
+    fn takes_no_turn_once_the_game_is_over() {
+        let (boards, _) = play(3, 7);
+        let last = boards[boards.len() - 1];
+        let mut agents: [Box<dyn Agent>; 3] = [
+            Box::new(RandomAgent::new(StdRng::seed_from_u64(7))),
+            Box::new(GreedyAgent::new(
+                GreedyStyle::BALANCED,
+                StdRng::seed_from_u64(7),
+            )),
+            Box::new(IsmctsAgent::new(
+                SearchLimits::DEFAULT,
+                StdRng::seed_from_u64(7),
+            )),
+        ];
+        for agent in &mut agents {
+            assert_eq!(
+                agent.choose_action(&last.view_for(last.next_player())),
+                None
+            );
+            assert!(take_agent_turn(last, agent.as_mut()).is_none());
+        }
+    }
+
+    #[test]
+    fn same_seed_plays_the_same_game() {
+        assert_eq!(play(4, 23), play(4, 23));
+    }
+}
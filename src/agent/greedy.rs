@@ -0,0 +1,185 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use super::Agent;
+use crate::{take_turn, Action, Board, Crew, CrewCounts, PlayerView, TurnResult};
+
+/// Points for each crew member the agent holds of the crew that wins the game,
+/// and against each one it holds of the crew that loses it.
+const RANKING_WEIGHT: i32 = 4;
+
+/// How a [`GreedyAgent`] weighs fighting against negotiating.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GreedyStyle {
+    /// Points for each crew member a battle removes from a zone.
+    pub aggression: u8,
+    /// Points for starting a negotiation rather than leaving the board as it is.
+    pub negotiation: u8,
+}
+
+impl GreedyStyle {
+    /// A style that fights and negotiates about as often as each other.
+    pub const BALANCED: Self = Self {
+        aggression: 1,
+        negotiation: 2,
+    };
+}
+
+impl Default for GreedyStyle {
+    fn default() -> Self {
+        Self::BALANCED
+    }
+}
+
+/// An agent that looks one action ahead and takes the one that leaves the best board.
+///
+/// A board is worth a point for each zone a crew controls for each member of
+/// that crew the agent holds, so the crew it holds most of counts the most.
+/// On top of that it is worth four points for each member of the winning crew
+/// the agent holds, and four points less for each member of the losing crew.
+/// The [`GreedyStyle`] adds points for battles and negotiations.
+///
+/// The agent only knows its own hand, so it judges the board as if the other
+/// hands were empty. Ties are broken at random.
+#[derive(Clone, Debug)]
+pub struct GreedyAgent<R> {
+    style: GreedyStyle,
+    rng: R,
+}
+
+impl<R: Rng> GreedyAgent<R> {
+    /// Creates an agent that plays in the given style and breaks ties with `rng`.
+    pub const fn new(style: GreedyStyle, rng: R) -> Self {
+        Self { style, rng }
+    }
+
+    /// Returns the style the agent plays in.
+    pub const fn style(&self) -> GreedyStyle {
+        self.style
+    }
+
+    fn value(&self, view: &PlayerView, board: &Board, action: Action) -> i32 {
+        let bonus = match action {
+            Action::Battle(_, _, rogues, bullies, goons) => {
+                i32::from(self.style.aggression)
+                    * (i32::from(rogues) + i32::from(bullies) + i32::from(goons))
+            }
+            Action::StartNegotiation => {
+                return evaluate(board, view.hand()) + i32::from(self.style.negotiation)
+            }
+            Action::Recruit(..) | Action::March(..) | Action::EndNegotiation(_) => 0,
+        };
+        match take_turn(*board, action) {
+            TurnResult(after, Ok(_)) => evaluate(&after, after.hand(view.viewer())) + bonus,
+            TurnResult(_, Err(_)) => i32::MIN,
+        }
+    }
+}
+
+impl<R: Rng> Agent for GreedyAgent<R> {
+    fn choose_action(&mut self, view: &PlayerView) -> Option<Action> {
+        let board = view.known_board();
+        let scored: Vec<(Action, i32)> = view
+            .legal_actions()
+            .into_iter()
+            .map(|action| (action, self.value(view, &board, action)))
+            .collect();
+        let best = scored.iter().map(|&(_, value)| value).max()?;
+        let best: Vec<Action> = scored
+            .into_iter()
+            .filter(|&(_, value)| value == best)
+            .map(|(action, _)| action)
+            .collect();
+        best.choose(&mut self.rng).copied()
+    }
+}
+
+/// How much the board is worth to a player holding `hand`.
+fn evaluate(board: &Board, hand: CrewCounts) -> i32 {
+    let report = board.score_report();
+    let held = |crew: Crew| i32::from(hand.get(crew));
+
+    let zones: i32 = enum_iterator::all::<Crew>()
+        .map(|crew| i32::from(report.crew_totals.get(crew)) * held(crew))
+        .sum();
+    let winning = report
+        .winning_crew
+        .map_or(0, |control| RANKING_WEIGHT * held(control.crew));
+    let losing = report
+        .losing_crew
+        .map_or(0, |crew| RANKING_WEIGHT * held(crew));
+    zones + winning - losing
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{start_game_with_seed, take_agent_turn, Player, RandomAgent, Winner};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn greedy(style: GreedyStyle, seed: u64) -> GreedyAgent<StdRng> {
+        GreedyAgent::new(style, StdRng::seed_from_u64(seed))
+    }
+
+    /// Plays greedy agents as alpha and gamma against random agents as beta and delta.
+    fn play(seed: u64) -> (Board, Winner) {
+        let mut greedy = greedy(GreedyStyle::BALANCED, seed);
+        let mut random = RandomAgent::new(StdRng::seed_from_u64(seed));
+        let mut board = start_game_with_seed(4, seed).unwrap();
+        loop {
+            let seat = board.next_player();
+            let agent: &mut dyn Agent = if matches!(seat, Player::Alpha | Player::Gamma) {
+                &mut greedy
+            } else {
+                &mut random
+            };
+            let TurnResult(next, result) = take_agent_turn(board, agent).unwrap();
+            board = next;
+            if let Some(winner) = result.unwrap() {
+                return (board, winner);
+            }
+        }
+    }
+
+    #[test]
+    fn only_chooses_legal_actions() {
+        let (board, _) = play(3);
+        assert!(board.is_over());
+        assert_eq!(board.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn same_seed_plays_the_same_game() {
+        assert_eq!(play(8), play(8));
+    }
+
+    #[test]
+    fn negotiates_when_it_values_negotiation_most() {
+        let board = start_game_with_seed(3, 5).unwrap();
+        let view = board.view_for(board.next_player());
+        let style = GreedyStyle {
+            aggression: 0,
+            negotiation: u8::MAX,
+        };
+        assert_eq!(
+            greedy(style, 5).choose_action(&view),
+            Some(Action::StartNegotiation)
+        );
+    }
+
+    #[test]
+    fn fights_when_it_values_battle_most() {
+        let board = start_game_with_seed(3, 5).unwrap();
+        let view = board.view_for(board.next_player());
+        let style = GreedyStyle {
+            aggression: u8::MAX,
+            negotiation: 0,
+        };
+        assert!(matches!(
+            greedy(style, 5).choose_action(&view),
+            Some(Action::Battle(..))
+        ));
+    }
+}
@@ -0,0 +1,386 @@
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use super::Agent;
+use crate::{take_turn, Action, Board, Player, PlayerView, TurnResult, Winner};
+
+/// How strongly the search tries actions it knows little about over those that have done well.
+const EXPLORATION: f64 = std::f64::consts::SQRT_2;
+
+/// The most turns a playout takes before the board is scored as it stands.
+const MAX_PLAYOUT_TURNS: usize = 500;
+
+/// How long an [`IsmctsAgent`] searches before it chooses.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SearchLimits {
+    /// The most playouts to run, shared between the threads.
+    pub iterations: u32,
+    /// The most time to search for, if any.
+    pub time: Option<Duration>,
+    /// The number of threads that search, each growing its own tree.
+    pub threads: u8,
+}
+
+impl SearchLimits {
+    /// A thousand playouts on one thread, however long they take.
+    pub const DEFAULT: Self = Self {
+        iterations: 1000,
+        time: None,
+        threads: 1,
+    };
+}
+
+impl Default for SearchLimits {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// An agent that searches with information set Monte Carlo tree search.
+///
+/// Each playout deals the crew members the agent cannot see to the other hands
+/// and the bag at random, then plays the game out with random actions from there,
+/// so the search never relies on what is hidden. The action tried most often wins.
+///
+/// A time limit makes the choices depend on how fast the search runs,
+/// so only the iteration limit keeps them repeatable.
+#[derive(Clone, Debug)]
+pub struct IsmctsAgent<R> {
+    limits: SearchLimits,
+    rng: R,
+}
+
+impl<R: Rng> IsmctsAgent<R> {
+    /// Creates an agent that searches within `limits`, seeding its searches from `rng`.
+    pub const fn new(limits: SearchLimits, rng: R) -> Self {
+        Self { limits, rng }
+    }
+
+    /// Returns the limits the agent searches within.
+    pub const fn limits(&self) -> SearchLimits {
+        self.limits
+    }
+}
+
+impl<R: Rng> Agent for IsmctsAgent<R> {
+    fn choose_action(&mut self, view: &PlayerView) -> Option<Action> {
+        let legal = view.legal_actions();
+        if legal.len() <= 1 {
+            return legal.first().copied();
+        }
+
+        let threads = u32::from(self.limits.threads.max(1));
+        let deadline = self.limits.time.map(|time| Instant::now() + time);
+        let searches: Vec<(u64, u32)> = (0..threads)
+            .map(|thread| {
+                let share = self.limits.iterations / threads
+                    + u32::from(thread < self.limits.iterations % threads);
+                (self.rng.next_u64(), share)
+            })
+            .collect();
+
+        let trees: Vec<Tree> = std::thread::scope(|scope| {
+            // Every thread has to start before any is joined.
+            #[allow(clippy::needless_collect)]
+            let handles: Vec<_> = searches
+                .into_iter()
+                .map(|(seed, iterations)| {
+                    scope.spawn(move || {
+                        let mut rng = StdRng::seed_from_u64(seed);
+                        Tree::search(view, iterations, deadline, &mut rng)
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                })
+                .collect()
+        });
+
+        let visits =
+            |action: Action| -> u32 { trees.iter().map(|tree| tree.root_visits(action)).sum() };
+        legal.iter().copied().max_by_key(|&action| visits(action))
+    }
+}
+
+/// A node of the search tree: the action that led to it and how well it has done.
+struct Node {
+    action: Action,
+    /// The player who took the action. The root stands for the viewer before they act,
+    /// and its action is never taken.
+    player: Player,
+    visits: u32,
+    /// The number of playouts in which the action could have been taken.
+    availability: u32,
+    /// The sum of the rewards the player got from the playouts through this node.
+    reward: f64,
+    children: Vec<usize>,
+}
+
+impl Node {
+    fn upper_bound(&self) -> f64 {
+        let visits = f64::from(self.visits);
+        let exploration = (f64::from(self.availability).ln() / visits).sqrt();
+        EXPLORATION.mul_add(exploration, self.reward / visits)
+    }
+}
+
+struct Tree {
+    nodes: Vec<Node>,
+}
+
+impl Tree {
+    /// Grows a tree of the viewer's options until `iterations` playouts have run
+    /// or the deadline has passed.
+    fn search<R: Rng + ?Sized>(
+        view: &PlayerView,
+        iterations: u32,
+        deadline: Option<Instant>,
+        rng: &mut R,
+    ) -> Self {
+        let mut tree = Self {
+            nodes: vec![Node {
+                action: Action::StartNegotiation,
+                player: view.viewer(),
+                visits: 0,
+                availability: 0,
+                reward: 0.0,
+                children: Vec::new(),
+            }],
+        };
+        for _ in 0..iterations {
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                break;
+            }
+            tree.iterate(view.sample_determinization(rng), rng);
+        }
+        tree
+    }
+
+    /// Runs one playout on a determinized board and records its result.
+    fn iterate<R: Rng + ?Sized>(&mut self, mut board: Board, rng: &mut R) {
+        let mut path = Vec::new();
+        let mut node = 0;
+        let mut winner = None;
+
+        while winner.is_none() {
+            let legal = board.legal_actions();
+            if legal.is_empty() {
+                break;
+            }
+            let children = &self.nodes[node].children;
+            let untried: Vec<Action> = legal
+                .iter()
+                .copied()
+                .filter(|&action| {
+                    !children
+                        .iter()
+                        .any(|&child| self.nodes[child].action == action)
+                })
+                .collect();
+
+            let expanding = !untried.is_empty();
+            let child = if let Some(&action) = untried.choose(rng) {
+                self.expand(node, action, board.next_player())
+            } else {
+                self.select(node, &legal)
+            };
+            path.push(child);
+            node = child;
+
+            let TurnResult(next, result) = take_turn(board, self.nodes[child].action);
+            board = next;
+            winner = result.ok().flatten();
+            if expanding {
+                break;
+            }
+        }
+
+        let winner = winner.unwrap_or_else(|| playout(board, rng));
+        let num_players = f64::from(board.num_players());
+        for &child in &path {
+            let node = &mut self.nodes[child];
+            node.visits += 1;
+            node.reward += match winner {
+                Winner::Player(player) if player == node.player => 1.0,
+                Winner::Player(_) => 0.0,
+                Winner::Draw => 1.0 / num_players,
+            };
+        }
+    }
+
+    fn expand(&mut self, parent: usize, action: Action, player: Player) -> usize {
+        let child = self.nodes.len();
+        self.nodes.push(Node {
+            action,
+            player,
+            visits: 0,
+            availability: 1,
+            reward: 0.0,
+            children: Vec::new(),
+        });
+        self.nodes[parent].children.push(child);
+        child
+    }
+
+    /// Picks the child to follow among those legal on this board,
+    /// counting each of them as available.
+    fn select(&mut self, parent: usize, legal: &[Action]) -> usize {
+        let mut best = None;
+        let mut best_bound = f64::NEG_INFINITY;
+        for index in 0..self.nodes[parent].children.len() {
+            let child = self.nodes[parent].children[index];
+            if !legal.contains(&self.nodes[child].action) {
+                continue;
+            }
+            self.nodes[child].availability += 1;
+            let bound = self.nodes[child].upper_bound();
+            if bound > best_bound {
+                best = Some(child);
+                best_bound = bound;
+            }
+        }
+        best.unwrap_or(self.nodes[parent].children[0])
+    }
+
+    /// How many playouts began with the viewer taking `action`.
+    fn root_visits(&self, action: Action) -> u32 {
+        self.nodes[0]
+            .children
+            .iter()
+            .map(|&child| &self.nodes[child])
+            .find(|node| node.action == action)
+            .map_or(0, |node| node.visits)
+    }
+}
+
+/// Plays random actions until the game ends, or scores the board as it stands
+/// once the playout has run too long or no one can move.
+///
+/// Each turn picks a kind of action at random, then one action of that kind,
+/// so that the many ways to march or battle do not crowd out negotiating.
+fn playout<R: Rng + ?Sized>(mut board: Board, rng: &mut R) -> Winner {
+    for _ in 0..MAX_PLAYOUT_TURNS {
+        let legal = board.legal_actions();
+        let mut kinds = Vec::with_capacity(4);
+        for kind in legal.iter().map(std::mem::discriminant) {
+            if !kinds.contains(&kind) {
+                kinds.push(kind);
+            }
+        }
+        let Some(&kind) = kinds.choose(rng) else {
+            break;
+        };
+        let of_kind: Vec<Action> = legal
+            .into_iter()
+            .filter(|&action| std::mem::discriminant(&action) == kind)
+            .collect();
+        let Some(&action) = of_kind.choose(rng) else {
+            break;
+        };
+        let TurnResult(next, result) = take_turn(board, action);
+        board = next;
+        if let Ok(Some(winner)) = result {
+            return winner;
+        }
+    }
+    let leader = board
+        .score_report()
+        .ranking
+        .first()
+        .map(|standing| standing.player);
+    leader.map_or(Winner::Draw, Winner::Player)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{start_game_with_seed, Crew};
+
+    fn agent(iterations: u32, threads: u8) -> IsmctsAgent<StdRng> {
+        let limits = SearchLimits {
+            iterations,
+            time: None,
+            threads,
+        };
+        IsmctsAgent::new(limits, StdRng::seed_from_u64(17))
+    }
+
+    #[test]
+    fn chooses_a_legal_action() {
+        let board = start_game_with_seed(3, 2).unwrap();
+        let view = board.view_for(board.next_player());
+        let action = agent(50, 1).choose_action(&view).unwrap();
+        assert!(board.legal_actions().contains(&action));
+    }
+
+    #[test]
+    fn same_seed_makes_the_same_choice() {
+        let board = start_game_with_seed(3, 2).unwrap();
+        let view = board.view_for(board.next_player());
+        assert_eq!(
+            agent(60, 2).choose_action(&view),
+            agent(60, 2).choose_action(&view)
+        );
+    }
+
+    #[test]
+    fn stops_at_the_time_limit() {
+        let board = start_game_with_seed(4, 2).unwrap();
+        let view = board.view_for(board.next_player());
+        let limits = SearchLimits {
+            iterations: u32::MAX,
+            time: Some(Duration::from_millis(50)),
+            threads: 2,
+        };
+        let started = Instant::now();
+        let action = IsmctsAgent::new(limits, StdRng::seed_from_u64(1))
+            .choose_action(&view)
+            .unwrap();
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(board.legal_actions().contains(&action));
+    }
+
+    #[test]
+    fn finds_the_only_winning_action() {
+        // Rogues hold six zones, bullies three and goons two, and ending this negotiation
+        // ends the game. Only ending it with goons leaves alpha ahead of beta.
+        let board: Board = "1.0.0/1.0.0/1.0.0/1.0.0/1.0.0/1.0.0/0.1.0/0.1.0/0.1.0/0.0.1/0.0.1 \
+                            1.1.1/1.0.1 0.0.0 0.0.0 0.0.0 2 a n 1"
+            .parse()
+            .unwrap();
+        let wins = |action| {
+            let TurnResult(_, result) = take_turn(board, action);
+            result == Ok(Some(Winner::Player(Player::Alpha)))
+        };
+        let legal = board.legal_actions();
+        assert_eq!(legal.len(), 3);
+        assert_eq!(legal.iter().filter(|&&action| wins(action)).count(), 1);
+
+        let view = board.view_for(Player::Alpha);
+        for seed in 0..5 {
+            let mut agent = IsmctsAgent::new(SearchLimits::DEFAULT, StdRng::seed_from_u64(seed));
+            assert!(wins(agent.choose_action(&view).unwrap()));
+        }
+    }
+
+    #[test]
+    fn ends_a_negotiation_with_the_only_crew_it_holds() {
+        let board: Board = "2.0.0/0.2.0/0.0.2/0.0.0/0.0.0/0.0.0/0.0.0/0.0.0/0.0.0/0.0.0/0.0.0 \
+                            1.0.0/0.1.0 0.0.0 0.0.0 0.0.0 2 a n 0"
+            .parse()
+            .unwrap();
+        let view = board.view_for(Player::Alpha);
+        assert_eq!(
+            agent(10, 1).choose_action(&view),
+            Some(Action::EndNegotiation(Crew::Rogues))
+        );
+    }
+}
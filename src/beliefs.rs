@@ -0,0 +1,465 @@
+//! What one player can infer about the hands they cannot see.
+
+use crate::{Action, Crew, CrewCounts, HandWeights, Player, PlayerView, TurnResult};
+
+/// The least share of the unseen members of a crew the bag is ever expected to hold.
+const MIN_BAG_SHARE: f64 = 0.01;
+
+/// What a [`BeliefTracker`] believes about one hand: every hand it could be,
+/// each with the chance that it is.
+#[derive(Clone, PartialEq, Debug)]
+pub struct HandBelief {
+    size: u16,
+    /// The hands that are still possible, with chances that add up to one.
+    outcomes: Vec<(CrewCounts, f64)>,
+}
+
+impl HandBelief {
+    /// A hand that is known for certain.
+    fn certain(hand: CrewCounts) -> Self {
+        Self {
+            size: hand.total(),
+            outcomes: vec![(hand, 1.0)],
+        }
+    }
+
+    /// A hand of `size` dealt at random from the crew members in `unseen`.
+    fn dealt(size: u16, unseen: CrewCounts) -> Self {
+        let mut outcomes = Vec::new();
+        for rogues in 0..=unseen.rogues {
+            for bullies in 0..=unseen.bullies {
+                let Some(goons) = size.checked_sub(u16::from(rogues) + u16::from(bullies)) else {
+                    break;
+                };
+                let Ok(goons) = u8::try_from(goons) else {
+                    continue;
+                };
+                if goons > unseen.goons {
+                    continue;
+                }
+                let chance = choose(unseen.rogues, rogues)
+                    * choose(unseen.bullies, bullies)
+                    * choose(unseen.goons, goons);
+                let hand = CrewCounts {
+                    rogues,
+                    bullies,
+                    goons,
+                };
+                outcomes.push((hand, chance));
+            }
+        }
+        let mut belief = Self { size, outcomes };
+        belief.normalize();
+        belief
+    }
+
+    /// Returns the number of crew members in the hand.
+    #[must_use]
+    pub const fn size(&self) -> u16 {
+        self.size
+    }
+
+    /// Returns the fewest members of each crew the hand could hold.
+    #[must_use]
+    pub fn lower_bounds(&self) -> CrewCounts {
+        let fewest = |crew: Crew| {
+            self.outcomes
+                .iter()
+                .map(|&(hand, _)| hand.get(crew))
+                .min()
+                .unwrap_or(0)
+        };
+        CrewCounts {
+            rogues: fewest(Crew::Rogues),
+            bullies: fewest(Crew::Bullies),
+            goons: fewest(Crew::Goons),
+        }
+    }
+
+    /// Returns the chance that the hand is exactly `hand`.
+    #[must_use]
+    pub fn probability(&self, hand: CrewCounts) -> f64 {
+        self.outcomes
+            .iter()
+            .find(|&&(outcome, _)| outcome == hand)
+            .map_or(0.0, |&(_, chance)| chance)
+    }
+
+    /// Returns the number of members of a crew the hand holds on average.
+    #[must_use]
+    pub fn expected(&self, crew: Crew) -> f64 {
+        self.outcomes
+            .iter()
+            .map(|&(hand, chance)| f64::from(hand.get(crew)) * chance)
+            .sum()
+    }
+
+    /// Returns every hand it could be, each with the chance that it is.
+    #[must_use]
+    pub fn outcomes(&self) -> &[(CrewCounts, f64)] {
+        &self.outcomes
+    }
+
+    /// Updates the belief for the hand giving up a member of `crew`,
+    /// which rules out every hand without one.
+    fn play(&mut self, crew: Crew) {
+        self.size = self.size.saturating_sub(1);
+        self.outcomes = self
+            .outcomes
+            .iter()
+            .filter(|&&(hand, _)| hand.get(crew) > 0)
+            .map(|&(hand, chance)| (change(hand, crew, false), chance))
+            .collect();
+        self.normalize();
+    }
+
+    /// Updates the belief for the hand drawing one crew member from a bag
+    /// expected to hold `bag` members of each crew.
+    fn draw(&mut self, bag: [f64; 3]) {
+        self.size += 1;
+        let mut outcomes: Vec<(CrewCounts, f64)> = Vec::new();
+        for &(hand, chance) in &self.outcomes {
+            for crew in enum_iterator::all::<Crew>() {
+                let chance = chance * bag[crew as usize];
+                if chance <= 0.0 {
+                    continue;
+                }
+                let hand = change(hand, crew, true);
+                match outcomes.iter_mut().find(|(outcome, _)| *outcome == hand) {
+                    Some((_, total)) => *total += chance,
+                    None => outcomes.push((hand, chance)),
+                }
+            }
+        }
+        self.outcomes = outcomes;
+        self.normalize();
+    }
+
+    /// Rules out every hand that needs more of a crew than there are unseen,
+    /// starting over from a random deal if that leaves nothing or the size is wrong.
+    fn constrain(&mut self, size: u16, unseen: CrewCounts) {
+        if size != self.size {
+            *self = Self::dealt(size, unseen);
+            return;
+        }
+        self.outcomes.retain(|&(hand, _)| {
+            enum_iterator::all::<Crew>().all(|crew| hand.get(crew) <= unseen.get(crew))
+        });
+        if self.outcomes.is_empty() {
+            *self = Self::dealt(size, unseen);
+        } else {
+            self.normalize();
+        }
+    }
+
+    fn normalize(&mut self) {
+        self.outcomes.retain(|&(_, chance)| chance > 0.0);
+        let total: f64 = self.outcomes.iter().map(|&(_, chance)| chance).sum();
+        for (_, chance) in &mut self.outcomes {
+            *chance /= total;
+        }
+    }
+}
+
+/// Follows the actions of a game from one player's seat and keeps a [`HandBelief`]
+/// for every hand.
+///
+/// Each `Recruit`, `March` and `Battle` shows a crew member leaving a hand and each
+/// `EndNegotiation` shows one going back to the bag, so the hand that played it
+/// must have held one. A hand that negotiates draws from what the bag is
+/// expected to hold. Every hand is tracked on its own, and never holds more of
+/// a crew than the viewer cannot see.
+///
+/// The viewer's own hand is always known.
+#[derive(Clone)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct BeliefTracker {
+    view: PlayerView,
+    hands: [HandBelief; 5],
+}
+
+impl BeliefTracker {
+    /// Starts tracking from what the viewer can see, taking every other hand
+    /// to be dealt at random from the crew members they cannot see.
+    #[must_use]
+    pub fn new(view: PlayerView) -> Self {
+        let unseen = view.unseen();
+        let hands = [
+            Player::Alpha,
+            Player::Beta,
+            Player::Gamma,
+            Player::Delta,
+            Player::Epsilon,
+        ]
+        .map(|player| {
+            if player == view.viewer() {
+                HandBelief::certain(view.hand())
+            } else {
+                HandBelief::dealt(view.public().hand_size(player), unseen)
+            }
+        });
+        Self { view, hands }
+    }
+
+    /// Returns the player whose seat the actions are followed from.
+    #[must_use]
+    pub const fn viewer(&self) -> Player {
+        self.view.viewer()
+    }
+
+    /// Returns what the viewer could see after the last action.
+    #[must_use]
+    pub const fn view(&self) -> &PlayerView {
+        &self.view
+    }
+
+    /// Returns the belief about a player's hand.
+    #[must_use]
+    pub const fn hand(&self, player: Player) -> &HandBelief {
+        &self.hands[player as usize]
+    }
+
+    /// Updates every belief for the next player having taken `action`,
+    /// with `result` as returned by [`take_turn`](crate::take_turn).
+    ///
+    /// Only what the viewer can see of the board in `result` is used.
+    /// Actions that were rejected change nothing.
+    pub fn observe(&mut self, action: Action, result: &TurnResult) {
+        let TurnResult(board, Ok(_)) = result else {
+            return;
+        };
+        let actor = self.view.public().next_player();
+        let next = board.view_for(self.viewer());
+
+        if actor != self.viewer() {
+            match action {
+                Action::Recruit(crew, ..)
+                | Action::March(crew, ..)
+                | Action::Battle(crew, ..)
+                | Action::EndNegotiation(crew) => self.hands[actor as usize].play(crew),
+                Action::StartNegotiation => {
+                    if next.public().hand_size(actor) > self.view.public().hand_size(actor) {
+                        let bag = self.expected_bag();
+                        self.hands[actor as usize].draw(bag);
+                    }
+                }
+            }
+        }
+
+        self.view = next;
+        let unseen = self.view.unseen();
+        for player in enum_iterator::all::<Player>() {
+            if player == self.viewer() {
+                self.hands[player as usize] = HandBelief::certain(self.view.hand());
+            } else {
+                let size = self.view.public().hand_size(player);
+                self.hands[player as usize].constrain(size, unseen);
+            }
+        }
+    }
+
+    /// Returns weights for [`PlayerView::sample_weighted_determinization`]
+    /// that lean each hidden hand towards the crews it is expected to hold.
+    ///
+    /// Each weight is the share of the hand expected to be of that crew,
+    /// over the share of that crew among everything the viewer cannot see.
+    #[must_use]
+    pub fn hand_weights(&self) -> HandWeights {
+        let unseen = self.view.unseen();
+        let total = f64::from(unseen.total());
+        let mut weights = HandWeights::UNIFORM;
+        for player in enum_iterator::all::<Player>() {
+            let belief = self.hand(player);
+            if player == self.viewer() || belief.size() == 0 {
+                continue;
+            }
+            for crew in enum_iterator::all::<Crew>() {
+                let unseen = f64::from(unseen.get(crew));
+                if unseen > 0.0 {
+                    let share = belief.expected(crew) / f64::from(belief.size());
+                    weights.set(player, crew, share * total / unseen);
+                }
+            }
+        }
+        weights
+    }
+
+    /// How many members of each crew the bag is expected to hold: those the viewer
+    /// cannot see less those the other hands are expected to hold.
+    fn expected_bag(&self) -> [f64; 3] {
+        let unseen = self.view.unseen();
+        let mut bag = [0.0; 3];
+        for crew in enum_iterator::all::<Crew>() {
+            let held: f64 = enum_iterator::all::<Player>()
+                .filter(|&player| player != self.viewer())
+                .map(|player| self.hand(player).expected(crew))
+                .sum();
+            // The other hands are only guessed at, so every unseen crew keeps some chance.
+            let unseen = f64::from(unseen.get(crew));
+            bag[crew as usize] = (unseen - held).max(unseen * MIN_BAG_SHARE);
+        }
+        bag
+    }
+}
+
+/// The number of ways to pick `k` of `n`.
+fn choose(n: u8, k: u8) -> f64 {
+    (0..k).fold(1.0, |ways, i| ways * f64::from(n - i) / f64::from(i + 1))
+}
+
+/// Returns the hand with one more or one fewer member of a crew.
+const fn change(mut hand: CrewCounts, crew: Crew, more: bool) -> CrewCounts {
+    let count = match crew {
+        Crew::Rogues => &mut hand.rogues,
+        Crew::Bullies => &mut hand.bullies,
+        Crew::Goons => &mut hand.goons,
+    };
+    *count = if more {
+        count.saturating_add(1)
+    } else {
+        count.saturating_sub(1)
+    };
+    hand
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{start_game_with_rules, start_game_with_seed, take_turn, Board, RulesConfig, Zone};
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    fn total(belief: &HandBelief) -> f64 {
+        belief.outcomes().iter().map(|&(_, chance)| chance).sum()
+    }
+
+    #[test]
+    fn starts_from_a_random_deal() {
+        let board = start_game_with_seed(3, 4).unwrap();
+        let tracker = BeliefTracker::new(board.view_for(Player::Alpha));
+        assert_eq!(
+            tracker.hand(Player::Alpha).outcomes(),
+            &[(board.hand(Player::Alpha), 1.0)]
+        );
+
+        let beta = tracker.hand(Player::Beta);
+        assert_eq!(beta.size(), board.hand(Player::Beta).total());
+        assert!(close(total(beta), 1.0));
+        let expected: f64 = enum_iterator::all::<Crew>()
+            .map(|crew| beta.expected(crew))
+            .sum();
+        assert!(close(expected, f64::from(beta.size())));
+        assert!(beta.probability(board.hand(Player::Beta)) > 0.0);
+        assert!(tracker
+            .hand(Player::Delta)
+            .outcomes()
+            .iter()
+            .all(|&(hand, _)| hand.total() == 0));
+    }
+
+    /// Starts a two player game with no crew members in the zones and only `bag` in play.
+    fn small_game(seed: u64, hand_size: u8, bag: CrewCounts) -> Board {
+        let rules = RulesConfig {
+            hand_size,
+            zone_fill: 0,
+            bag,
+            ..RulesConfig::STANDARD
+        };
+        start_game_with_rules(2, seed, rules).unwrap()
+    }
+
+    #[test]
+    fn knows_a_hand_when_nothing_else_is_hidden() {
+        let bag = CrewCounts {
+            rogues: 2,
+            bullies: 2,
+            goons: 2,
+        };
+        let board = small_game(1, 3, bag);
+        let tracker = BeliefTracker::new(board.view_for(Player::Alpha));
+        let beta = board.hand(Player::Beta);
+        assert!(close(tracker.hand(Player::Beta).probability(beta), 1.0));
+        assert_eq!(tracker.hand(Player::Beta).lower_bounds(), beta);
+    }
+
+    #[test]
+    fn a_played_crew_must_have_been_held() {
+        let bag = CrewCounts {
+            rogues: 2,
+            bullies: 2,
+            goons: 1,
+        };
+        // Beta holds a rogue and a goon, and cannot see the other rogue and two bullies.
+        let board = small_game(2, 2, bag);
+        let mut tracker = BeliefTracker::new(board.view_for(Player::Beta));
+        let two_bullies = CrewCounts {
+            bullies: 2,
+            ..CrewCounts::default()
+        };
+        let alpha = tracker.hand(Player::Alpha);
+        let one_bully = CrewCounts {
+            bullies: 1,
+            ..CrewCounts::default()
+        };
+        assert!(close(alpha.probability(two_bullies), 1.0 / 3.0));
+        assert_eq!(alpha.lower_bounds(), one_bully);
+
+        let action = Action::Recruit(Crew::Rogues, Zone::Red);
+        let result = take_turn(board, action);
+        assert!(result.1.is_ok());
+        tracker.observe(action, &result);
+
+        let alpha = tracker.hand(Player::Alpha);
+        assert_eq!(alpha.size(), 1);
+        assert!(close(alpha.probability(one_bully), 1.0));
+        assert_eq!(alpha.lower_bounds(), one_bully);
+    }
+
+    #[test]
+    fn rejected_actions_change_nothing() {
+        let board = start_game_with_seed(3, 4).unwrap();
+        let mut tracker = BeliefTracker::new(board.view_for(Player::Beta));
+        let before = tracker.clone();
+        let action = Action::Battle(Crew::Rogues, Zone::Red, 5, 5, 5);
+        tracker.observe(action, &take_turn(board, action));
+        assert_eq!(tracker.hands, before.hands);
+    }
+
+    #[test]
+    fn follows_a_whole_game() {
+        let mut board = start_game_with_seed(4, 9).unwrap();
+        let mut tracker = BeliefTracker::new(board.view_for(Player::Gamma));
+        for _ in 0..200 {
+            let Some(&action) = board.legal_actions().first() else {
+                break;
+            };
+            let result = take_turn(board, action);
+            tracker.observe(action, &result);
+            board = result.0;
+            for player in enum_iterator::all::<Player>() {
+                let belief = tracker.hand(player);
+                assert_eq!(belief.size(), board.hand(player).total());
+                assert!(close(total(belief), 1.0));
+                assert!(belief.probability(board.hand(player)) > 0.0, "{player:?}");
+                let bounds = belief.lower_bounds();
+                let hand = board.hand(player);
+                assert!(enum_iterator::all::<Crew>().all(|crew| bounds.get(crew) <= hand.get(crew)));
+            }
+            if matches!(result.1, Ok(Some(_))) {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn weights_start_even() {
+        let board = start_game_with_seed(3, 4).unwrap();
+        let tracker = BeliefTracker::new(board.view_for(Player::Alpha));
+        let weights = tracker.hand_weights();
+        for crew in enum_iterator::all::<Crew>() {
+            assert!(close(weights.get(Player::Beta, crew), 1.0));
+        }
+    }
+}
@@ -3,29 +3,11 @@
 use tergiversators::*;
 
 fn translate_zone(input: &str) -> Option<Zone> {
-    match input {
-        "red" => Some(Zone::Red),
-        "blue" => Some(Zone::Blue),
-        "green" => Some(Zone::Green),
-        "orange" => Some(Zone::Orange),
-        "yellow" => Some(Zone::Yellow),
-        "cyan" => Some(Zone::Cyan),
-        "magenta" => Some(Zone::Magenta),
-        "purple" => Some(Zone::Purple),
-        "white" => Some(Zone::White),
-        "black" => Some(Zone::Black),
-        "gray" => Some(Zone::Gray),
-        _ => None,
-    }
+    input.parse().ok()
 }
 
 fn translate_crew(input: &str) -> Option<Crew> {
-    match input {
-        "bullies" => Some(Crew::Bullies),
-        "goons" => Some(Crew::Goons),
-        "rogues" => Some(Crew::Rogues),
-        _ => None,
-    }
+    input.parse().ok()
 }
 
 fn get_zone() -> Zone {
@@ -119,16 +101,30 @@ fn translate_action(input: &str) -> Option<Action> {
         "march" => Some(march()),
         "negotiate" => Some(Action::StartNegotiation),
         "battle" => Some(battle()),
-        _=> None,
+        _ => input.parse().ok(),
     }
 }
 
-fn get_action() -> Action {
+enum Command {
+    Act(Action),
+    Undo,
+    Redo,
+}
+
+fn translate_command(input: &str) -> Option<Command> {
+    match input {
+        "undo" => Some(Command::Undo),
+        "redo" => Some(Command::Redo),
+        _ => translate_action(input).map(Command::Act),
+    }
+}
+
+fn get_command() -> Command {
     let mut line = String::new();
     loop {
         std::io::stdin().read_line(&mut line).unwrap();
-        if let Some(action) = translate_action(line.trim()) {
-            return action;
+        if let Some(command) = translate_command(line.trim()) {
+            return command;
         }
         println!("Invalid action");
         line.clear();
@@ -151,31 +147,42 @@ fn main() {
     println!("Number of players?");
     std::io::stdin().read_line(&mut line).unwrap();
     let num_players = line.trim().parse::<u8>().unwrap();
-    let mut board = start_game(num_players).unwrap();
-    let mut result: Result<Option<Winner>, Error> ;
-    let mut negotiation = false;
+    let mut game = Game::new(start_game(num_players).unwrap());
     loop {
-        println!("Board: {:?}", board);
-        let action = if negotiation {
-            end_negotiation()
+        println!("Board: {:?}", game.board());
+        let command = if game.board().is_negotiating() {
+            Command::Act(end_negotiation())
         } else {
             println!("What action?");
-            get_action()
+            get_command()
         };
-        negotiation = matches!(action, Action::StartNegotiation);
-        TurnResult(board, result) = take_turn(board, action);
-        match result {
+        let action = match command {
+            Command::Act(action) => action,
+            Command::Undo => {
+                if game.undo().is_none() {
+                    println!("Nothing to undo");
+                }
+                continue;
+            }
+            Command::Redo => {
+                if game.redo().is_none() {
+                    println!("Nothing to redo");
+                }
+                continue;
+            }
+        };
+        match game.play(action) {
             Ok(Some(winner)) => {
                 println!("Winner: {:?}", get_winner(winner));
+                for placing in game.board().final_ranking().unwrap_or_default() {
+                    println!("{}: {:?}", placing.place, placing.player);
+                }
                 break;
-            },
-            Err(Error{reason, ..}) => {
-                println!("Error: {:?}", reason);
-            },
+            }
+            Err(error) => {
+                println!("Error: {error}");
+            }
             _ => (),
         }
     }
-
-
-
-}
\ No newline at end of file
+}
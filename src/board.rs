@@ -1,33 +1,44 @@
-use crate::Error;
-use crate::{Action, Crew, Player, TurnResult, Winner, Zone};
+use crate::{
+    Action, Crew, CrewCounts, EmptyBagRule, Player, RulesConfig, TiebreakOrder, TurnResult,
+    UnusedSeats, Winner, Zone,
+};
+use crate::{Error, ErrorKind, SetupError};
 use bag::Bag;
 use board_space::BoardSpace;
+use map::{Layout, MAX_ZONES};
 use rand::seq::SliceRandom;
+use rng::BoardRng;
 
 mod bag;
 mod board_space;
 mod constants;
+mod invariants;
+mod map;
+mod notation;
+mod rng;
+mod score;
+#[cfg(feature = "serde")]
+mod versioned;
+mod view;
+
+pub use map::{Map, ZoneSpec};
+pub use score::{Control, DecidedBy, Placing, ScoreReport, SeparatedBy, Standing, ZoneControl};
+pub use view::{HandWeights, PlayerView, SpectatorView};
 
 #[allow(clippy::wildcard_imports)]
 use constants::*;
 
 /// The board is the game state. It tracks everything about the game.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(debug_assertions, derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(remote = "Self"))]
 pub struct Board {
     bag: Bag,
+    rng: BoardRng,
 
-    red: BoardSpace,
-    green: BoardSpace,
-    blue: BoardSpace,
-    orange: BoardSpace,
-    yellow: BoardSpace,
-    cyan: BoardSpace,
-    magenta: BoardSpace,
-    purple: BoardSpace,
-    white: BoardSpace,
-    black: BoardSpace,
-    gray: BoardSpace,
+    /// The zones of the map, indexed by [`Zone::index`]; those past the map stay empty.
+    zones: [BoardSpace; MAX_ZONES],
+    layout: Layout,
 
     alpha: BoardSpace,
     beta: BoardSpace,
@@ -42,21 +53,14 @@ pub struct Board {
     next_player: Player,
     current_negotiation: bool,
     consecutive_negotiations: u8,
+
+    rules: RulesConfig,
 }
 
 impl Board {
-    const EMPTY: Self = Self{
-        red: BoardSpace::home_base(Crew::Rogues),
-        green: BoardSpace::home_base(Crew::Goons),
-        blue: BoardSpace::home_base(Crew::Bullies),
-        orange: BoardSpace::EMPTY,
-        yellow: BoardSpace::EMPTY,
-        cyan: BoardSpace::EMPTY,
-        magenta: BoardSpace::EMPTY,
-        purple: BoardSpace::EMPTY,
-        white: BoardSpace::EMPTY,
-        black: BoardSpace::EMPTY,
-        gray: BoardSpace::EMPTY,
+    const EMPTY: Self = Self {
+        zones: Layout::STANDARD.starting_spaces(),
+        layout: Layout::STANDARD,
         alpha: BoardSpace::EMPTY,
         beta: BoardSpace::EMPTY,
         gamma: BoardSpace::EMPTY,
@@ -65,57 +69,213 @@ impl Board {
         swords: BoardSpace::EMPTY,
         flags: BoardSpace::EMPTY,
         bag: Bag::EMPTY,
+        rng: BoardRng::from_seed(0),
         next_player: Player::Alpha,
         current_negotiation: false,
         consecutive_negotiations: 0,
         num_players: 2,
+        rules: RulesConfig::STANDARD,
     };
 
-    pub(crate) fn build(num_players: u8) -> Result<Self, &'static str> {
+    pub(crate) fn build(num_players: u8, seed: u64) -> Result<Self, SetupError> {
+        Self::build_with_rules(num_players, seed, RulesConfig::STANDARD)
+    }
+
+    pub(crate) fn build_with_rules(
+        num_players: u8,
+        seed: u64,
+        rules: RulesConfig,
+    ) -> Result<Self, SetupError> {
+        Self::build_on_layout(num_players, seed, rules, Layout::STANDARD)
+    }
+
+    pub(crate) fn build_on_map(
+        num_players: u8,
+        seed: u64,
+        rules: RulesConfig,
+        map: &Map,
+    ) -> Result<Self, SetupError> {
+        Self::build_on_layout(num_players, seed, rules, map.layout())
+    }
+
+    fn build_on_layout(
+        num_players: u8,
+        seed: u64,
+        rules: RulesConfig,
+        layout: Layout,
+    ) -> Result<Self, SetupError> {
         if !(2..=5).contains(&num_players) {
-            return Err(Error::BAD_PLAYER_COUNT);
+            return Err(SetupError::BadPlayerCount(num_players));
+        }
+        if rules.negotiations_to_end(num_players) == 0 {
+            return Err(SetupError::NoNegotiationsToEnd);
         }
+        let seats = match rules.unused_seats {
+            UnusedSeats::Empty => num_players.into(),
+            UnusedSeats::Dealt => enum_iterator::cardinality::<Player>(),
+        };
+        let zones_to_fill = layout
+            .zones()
+            .filter(|&zone| layout.home_base(zone).is_none());
+        let needed = zones_to_fill.count() * usize::from(rules.zone_fill)
+            + seats * usize::from(rules.hand_size);
+        let available = rules.bag.total();
+        if needed > available.into() {
+            return Err(SetupError::NotEnoughCrew {
+                needed: u16::try_from(needed).unwrap_or(u16::MAX),
+                available,
+            });
+        }
+
         let mut board = Self {
             num_players,
+            rng: BoardRng::from_seed(seed),
+            rules,
+            zones: layout.starting_spaces(),
+            layout,
             ..Self::EMPTY
         };
-        board.setup(num_players.into());
+        if let Some((crew, count)) = board.too_many_crew() {
+            return Err(SetupError::TooManyCrew { crew, count });
+        }
+        board.setup(seats);
         Ok(board)
     }
 
-    fn get_space_mut(&mut self, zone: Zone) -> &mut BoardSpace {
-        match zone {
-            Zone::Red => &mut self.red,
-            Zone::Orange => &mut self.orange,
-            Zone::Yellow => &mut self.yellow,
-            Zone::Green => &mut self.green,
-            Zone::Cyan => &mut self.cyan,
-            Zone::Blue => &mut self.blue,
-            Zone::Magenta => &mut self.magenta,
-            Zone::Purple => &mut self.purple,
-            Zone::White => &mut self.white,
-            Zone::Black => &mut self.black,
-            Zone::Gray => &mut self.gray,
+    /// Returns the crew members in a zone.
+    /// Zones that are not on the map hold none.
+    #[must_use]
+    pub fn zone(&self, zone: Zone) -> CrewCounts {
+        if self.layout.contains(zone) {
+            (*self.get_space(zone)).into()
+        } else {
+            CrewCounts::default()
+        }
+    }
+
+    /// Returns every zone on the map, in order.
+    pub fn zones(&self) -> impl Iterator<Item = Zone> + Clone {
+        self.layout.zones()
+    }
+
+    /// Returns the zones adjacent to a zone on the map, in order.
+    pub fn neighbors(&self, zone: Zone) -> impl Iterator<Item = Zone> + Clone {
+        self.layout.neighbors(zone)
+    }
+
+    /// Returns whether crew members can march from one zone to the other on the map.
+    #[must_use]
+    pub const fn is_adjacent(&self, from: Zone, to: Zone) -> bool {
+        self.layout.is_adjacent(from, to)
+    }
+
+    /// Returns the crew members in a player's hand.
+    /// Players that are not seated hold none.
+    #[must_use]
+    pub fn hand(&self, player: Player) -> CrewCounts {
+        (*self.get_hand(player)).into()
+    }
+
+    /// Returns the crew members in the bag.
+    #[must_use]
+    pub fn bag(&self) -> CrewCounts {
+        self.bag.into()
+    }
+
+    /// Returns the crew members played to battle.
+    #[must_use]
+    pub fn swords(&self) -> CrewCounts {
+        self.swords.into()
+    }
+
+    /// Returns the crew members played to march.
+    #[must_use]
+    pub fn flags(&self) -> CrewCounts {
+        self.flags.into()
+    }
+
+    /// Returns the number of players in the game.
+    #[must_use]
+    pub const fn num_players(&self) -> u8 {
+        self.num_players
+    }
+
+    /// Returns the player whose turn it is.
+    #[must_use]
+    pub const fn next_player(&self) -> Player {
+        self.next_player
+    }
+
+    /// Returns whether a negotiation is waiting for its `EndNegotiation`.
+    #[must_use]
+    pub const fn is_negotiating(&self) -> bool {
+        self.current_negotiation
+    }
+
+    /// Returns the number of negotiations in a row.
+    #[must_use]
+    pub const fn consecutive_negotiations(&self) -> u8 {
+        self.consecutive_negotiations
+    }
+
+    /// Returns whether there have been enough negotiations in a row to end the game.
+    /// See [`GameEnd`](crate::GameEnd).
+    #[must_use]
+    pub const fn is_over(&self) -> bool {
+        self.consecutive_negotiations >= self.rules.negotiations_to_end(self.num_players)
+    }
+
+    /// The tallies that break a tie for control, in the order the rules check them.
+    const fn tiebreaks(&self) -> [(BoardSpace, DecidedBy); 2] {
+        let swords = (self.swords, DecidedBy::Swords);
+        let flags = (self.flags, DecidedBy::Flags);
+        match self.rules.tiebreak {
+            TiebreakOrder::SwordsThenFlags => [swords, flags],
+            TiebreakOrder::FlagsThenSwords => [flags, swords],
         }
     }
 
+    /// Returns the rules the game is played with.
+    #[must_use]
+    pub const fn rules(&self) -> RulesConfig {
+        self.rules
+    }
+
+    /// Returns the seated players, in seat order.
+    pub fn active_players(&self) -> impl Iterator<Item = Player> + Clone {
+        enum_iterator::all::<Player>().take(self.num_players.into())
+    }
+
+    /// The state of the random number generator, which can seed an identical generator.
+    pub(crate) const fn rng_state(&self) -> u64 {
+        self.rng.state()
+    }
+
+    pub(crate) const fn set_rng_state(&mut self, state: u64) {
+        self.rng = BoardRng::from_seed(state);
+    }
+
+    /// A map for the board's layout, naming its zones by index
+    /// unless it is the standard layout.
+    pub(crate) fn default_map(&self) -> Map {
+        Map::for_layout(self.layout)
+    }
+
+    /// Whether the board is played on the given map.
+    pub(crate) fn is_on_map(&self, map: &Map) -> bool {
+        self.layout == map.layout()
+    }
+
+    /// The space for a zone. Zones past the map are always empty.
+    const fn get_space_mut(&mut self, zone: Zone) -> &mut BoardSpace {
+        &mut self.zones[zone.index()]
+    }
+
     const fn get_space(&self, zone: Zone) -> &BoardSpace {
-        match zone {
-            Zone::Red => &self.red,
-            Zone::Orange => &self.orange,
-            Zone::Yellow => &self.yellow,
-            Zone::Green => &self.green,
-            Zone::Cyan => &self.cyan,
-            Zone::Blue => &self.blue,
-            Zone::Magenta => &self.magenta,
-            Zone::Purple => &self.purple,
-            Zone::White => &self.white,
-            Zone::Black => &self.black,
-            Zone::Gray => &self.gray,
-        }
-    }
-
-    fn get_hand_mut(&mut self, player: Player) -> &mut BoardSpace {
+        &self.zones[zone.index()]
+    }
+
+    const fn get_hand_mut(&mut self, player: Player) -> &mut BoardSpace {
         match player {
             Player::Alpha => &mut self.alpha,
             Player::Beta => &mut self.beta,
@@ -135,56 +295,74 @@ impl Board {
         }
     }
 
-    fn setup(&mut self, num_players: usize) {
-        let mut peices = [Crew::Rogues; 57];
-        peices.copy_from_slice(&DEFAULT_BAG);
-        peices.shuffle(&mut rand::thread_rng());
+    /// Deals the crew members of the bag the rules start with to the zones
+    /// and the hands of `seats` seats, leaving the rest in the bag.
+    fn setup(&mut self, seats: usize) {
+        let mut pieces: Vec<Crew> = [Crew::Bullies, Crew::Goons, Crew::Rogues]
+            .into_iter()
+            .flat_map(|crew| std::iter::repeat_n(crew, self.rules.bag.get(crew).into()))
+            .collect();
+        pieces.shuffle(&mut self.rng);
+        let mut pieces = pieces.into_iter();
 
-        for (zone, crews) in enum_iterator::all::<Zone>()
-            .skip(3)
-            .zip(peices.chunks_exact(2))
+        let layout = self.layout;
+        for zone in layout
+            .zones()
+            .filter(|&zone| layout.home_base(zone).is_none())
         {
-            self.get_space_mut(zone).add_crew(crews[0], 1);
-            self.get_space_mut(zone).add_crew(crews[1], 1);
+            for crew in pieces.by_ref().take(self.rules.zone_fill.into()) {
+                self.get_space_mut(zone).add_crew(crew, 1);
+            }
         }
 
-        for (player, crews) in enum_iterator::all::<Player>()
-            .take(num_players)
-            .zip(peices[ZONES_TO_FILL * 2..].chunks_exact(8))
-        {
-            let hand = self.get_hand_mut(player);
-            for &f in crews {
-                hand.add_crew(f, 1);
+        for player in enum_iterator::all::<Player>().take(seats) {
+            for crew in pieces.by_ref().take(self.rules.hand_size.into()) {
+                self.get_hand_mut(player).add_crew(crew, 1);
             }
         }
 
-        self.bag = Bag::from_slice(&peices[(ZONES_TO_FILL * 2 + num_players * 8)..]);
+        self.bag = Bag::from_slice(pieces.as_slice());
+    }
+
+    /// Returns the board with a zone holding the given crew members.
+    #[cfg(test)]
+    const fn with_zone(mut self, zone: Zone, space: BoardSpace) -> Self {
+        self.zones[zone.index()] = space;
+        self
+    }
+
+    fn play_crew(&mut self, player: Player, crew: Crew) -> Result<(), ErrorKind> {
+        self.get_hand_mut(player)
+            .subtract_crew(crew, 1)
+            .map_err(|shortfall| shortfall.in_hand(player))
     }
 
-    fn play_crew(&mut self, player: Player, crew: Crew) -> Result<(), &'static str> {
-        self.get_hand_mut(player).subtract_crew(crew, 1)
+    fn check_hand(&self, player: Player, crew: Crew) -> Result<(), ErrorKind> {
+        self.get_hand(player)
+            .check_crew(crew, 1)
+            .map_err(|shortfall| shortfall.in_hand(player))
     }
 
+    fn check_zone(&self, zone: Zone, crew: Crew, amount: u8) -> Result<(), ErrorKind> {
+        self.get_space(zone)
+            .check_crew(crew, amount)
+            .map_err(|shortfall| shortfall.in_zone(zone))
+    }
+
+    fn take_from_zone(&mut self, zone: Zone, crew: Crew, amount: u8) -> Result<(), ErrorKind> {
+        self.get_space_mut(zone)
+            .subtract_crew(crew, amount)
+            .map_err(|shortfall| shortfall.in_zone(zone))
+    }
+
+    /// Passes the turn to the next seated player, wrapping around after the last seat.
     fn advance_turn(&mut self) {
-        self.next_player = match self.next_player {
-            Player::Alpha => Player::Beta,
-            Player::Beta => Player::Gamma,
-            Player::Gamma => {
-                if self.num_players > 2 {
-                    Player::Delta
-                } else {
-                    Player::Alpha
-                }
-            }
-            Player::Delta => {
-                if self.num_players > 3 {
-                    Player::Epsilon
-                } else {
-                    Player::Alpha
-                }
-            }
-            Player::Epsilon => Player::Alpha,
-        };
+        let current = self.next_player;
+        self.next_player = self
+            .active_players()
+            .skip_while(|&player| player != current)
+            .nth(1)
+            .unwrap_or(Player::Alpha);
     }
 
     fn battle(
@@ -195,29 +373,31 @@ impl Board {
         rogues: u8,
         bullies: u8,
         goons: u8,
-    ) -> Result<(), &'static str> {
+    ) -> Result<(), ErrorKind> {
         if match crew {
             Crew::Rogues => rogues,
             Crew::Bullies => bullies,
             Crew::Goons => goons,
         } > 0
         {
-            return Err(Error::CANNOT_REMOVE_FROM_ATTACKING_FACTION);
-        };
-        let removal = rogues + bullies + goons;
+            return Err(ErrorKind::RemovingAttackingCrew(crew));
+        }
+        let removal = rogues.saturating_add(bullies).saturating_add(goons);
         if removal == 0 {
-            return Err(Error::MUST_REMOVE_WHEN_ATTACKING);
+            return Err(ErrorKind::NothingRemoved);
         }
 
-        self.play_crew(player, crew)?;
-
-        self.get_space(zone).check_crew(crew, removal)?;
+        self.check_hand(player, crew)?;
+        self.check_zone(zone, crew, removal)?;
+        for (target, amount) in enum_iterator::all::<Crew>().zip([rogues, bullies, goons]) {
+            self.check_zone(zone, target, amount)?;
+        }
 
+        self.play_crew(player, crew)?;
         self.swords.add_crew(crew, 1);
-
         for (crew, &amount) in enum_iterator::all::<Crew>().zip([rogues, bullies, goons].iter()) {
-            self.get_space_mut(zone).subtract_crew(crew, amount)?;
-            self.bag.replace(crew);
+            self.take_from_zone(zone, crew, amount)?;
+            self.bag.replace(crew, amount);
         }
 
         Ok(())
@@ -230,81 +410,173 @@ impl Board {
         from: Zone,
         to: Zone,
         amount: u8,
-    ) -> Result<(), &'static str> {
-        if !ADJACENCIES.contains(&(from, to)) {
-            return Err(Error::CANNOT_MARCH_FROM_TO);
+    ) -> Result<(), ErrorKind> {
+        if !self.layout.is_adjacent(from, to) {
+            return Err(ErrorKind::NotAdjacent { from, to });
         }
+        self.check_hand(player, crew)?;
+        self.check_zone(from, crew, amount)?;
+
         self.play_crew(player, crew)?;
-        self.get_space_mut(from).subtract_crew(crew, amount)?;
+        self.take_from_zone(from, crew, amount)?;
         self.flags.add_crew(crew, 1);
         self.get_space_mut(to).add_crew(crew, amount);
         Ok(())
     }
 
-    // This will never return an error, but the signature should match the other methods
-    #[allow(clippy::unnecessary_wraps)]
-    fn start_negotiation(&mut self, player: Player) -> Result<(), &'static str> {
-        let crew = self.bag.draw();
-        self.get_hand_mut(player).add_crew(crew, 1);
+    /// Whether the player can draw for a negotiation, or negotiate without drawing
+    /// if the rules allow it and they hold a crew member to end it with.
+    fn can_negotiate(&self, player: Player) -> bool {
+        self.can_negotiate_with(self.hand(player), self.bag.is_empty())
+    }
+
+    fn can_negotiate_with(&self, hand: CrewCounts, bag_is_empty: bool) -> bool {
+        !bag_is_empty || (self.rules.empty_bag == EmptyBagRule::NoDraw && hand.total() > 0)
+    }
+
+    fn start_negotiation(&mut self, player: Player) -> Result<(), ErrorKind> {
+        if !self.can_negotiate(player) {
+            return Err(ErrorKind::EmptyBag);
+        }
+        if let Some(crew) = self.bag.draw(&mut self.rng) {
+            self.get_hand_mut(player).add_crew(crew, 1);
+        }
         self.current_negotiation = true;
         Ok(())
     }
 
-    fn end_negotiation(&mut self, player: Player, crew: Crew) -> Result<(), &'static str> {
+    fn end_negotiation(&mut self, player: Player, crew: Crew) -> Result<(), ErrorKind> {
+        if !self.current_negotiation {
+            return Err(ErrorKind::NoNegotiationInProgress);
+        }
+        self.check_hand(player, crew)?;
+
         self.play_crew(player, crew)?;
-        self.bag.replace(crew);
+        self.bag.replace(crew, 1);
         self.current_negotiation = false;
-        self.consecutive_negotiations += 1;
+        self.consecutive_negotiations = self.consecutive_negotiations.saturating_add(1);
         Ok(())
     }
 
-    fn recruit(&mut self, player: Player, crew: Crew, zone: Zone) -> Result<(), &'static str> {
+    fn recruit(&mut self, player: Player, crew: Crew, zone: Zone) -> Result<(), ErrorKind> {
+        self.check_hand(player, crew)?;
+
         self.play_crew(player, crew)?;
         self.get_space_mut(zone).add_crew(crew, 1);
         Ok(())
     }
 
-    fn score(&self) -> Option<Player> {
-        let mut scores = BoardSpace::default();
-        let num_players = self.num_players.into();
+    /// Lists every action the next player may take.
+    ///
+    /// Nothing is listed once the game is over.
+    /// While a negotiation is in progress, only `EndNegotiation` is listed.
+    /// `StartNegotiation` is only listed when the bag has a crew member to draw,
+    /// or when the [`EmptyBagRule`] lets the player negotiate without drawing.
+    #[must_use]
+    pub fn legal_actions(&self) -> Vec<Action> {
+        self.legal_actions_with(self.hand(self.next_player), self.bag.is_empty())
+    }
+
+    /// Lists the actions the next player may take if they hold `hand`,
+    /// which only depends on the bag through whether it is empty.
+    fn legal_actions_with(&self, hand: CrewCounts, bag_is_empty: bool) -> Vec<Action> {
+        if self.is_over() {
+            return Vec::new();
+        }
+        let crews = enum_iterator::all::<Crew>().filter(|&crew| hand.get(crew) > 0);
+
+        if self.current_negotiation {
+            return crews.map(Action::EndNegotiation).collect();
+        }
 
-        for zone in enum_iterator::all::<Zone>() {
-            if let Some(crew) = self.get_space(zone).controlling_crew(self.swords, self.flags) {
-                scores.add_crew(crew, 1);
+        let mut actions = Vec::new();
+        for crew in crews {
+            for zone in self.zones() {
+                actions.push(Action::Recruit(crew, zone));
             }
+            for from in self.zones() {
+                for to in self.layout.neighbors(from) {
+                    for amount in 0..=self.get_space(from).get_crew(crew) {
+                        actions.push(Action::March(crew, from, to, amount));
+                    }
+                }
+            }
+            for zone in self.zones() {
+                self.push_battles(&mut actions, crew, zone);
+            }
+        }
+        if self.can_negotiate_with(hand, bag_is_empty) {
+            actions.push(Action::StartNegotiation);
         }
+        actions
+    }
 
-        let winning_crew = scores.controlling_crew(self.swords, self.flags)?;
-        let losing_crew = scores.loser();
+    fn push_battles(&self, actions: &mut Vec<Action>, crew: Crew, zone: Zone) {
+        let space = *self.get_space(zone);
+        let attackers = space.get_crew(crew);
+        let limit = |target: Crew| {
+            if target == crew {
+                0
+            } else {
+                space.get_crew(target)
+            }
+        };
 
-        let play_order: Vec<Player> = enum_iterator::all::<Player>()
-            .take(num_players)
-            .cycle()
-            .skip_while(|&p| p != self.next_player)
-            .take(num_players)
-            .collect();
+        for rogues in 0..=limit(Crew::Rogues) {
+            for bullies in 0..=limit(Crew::Bullies) {
+                for goons in 0..=limit(Crew::Goons) {
+                    let removed = u16::from(rogues) + u16::from(bullies) + u16::from(goons);
+                    if (1..=u16::from(attackers)).contains(&removed) {
+                        actions.push(Action::Battle(crew, zone, rogues, bullies, goons));
+                    }
+                }
+            }
+        }
+    }
 
-        let mut players: Vec<Player> = enum_iterator::all::<Player>().collect();
-        players.sort_unstable_by(|&a, &b| {
-            let a_hand = self.get_hand(a);
-            let b_hand = self.get_hand(b);
-            BoardSpace::winning_sort(*a_hand, *b_hand, winning_crew, losing_crew).then_with(|| {
-                play_order
-                    .iter()
-                    .position(|&p| p == a)
-                    .cmp(&play_order.iter().position(|&p| p == b))
-            })
-        });
+    fn score(&self) -> Option<Player> {
+        self.score_report()
+            .ranking
+            .first()
+            .map(|standing| standing.player)
+    }
 
-        Some(players[0])
+    /// The first zone the action names that is not on the map.
+    fn unknown_zone(&self, action: Action) -> Option<Zone> {
+        let zones = match action {
+            Action::Recruit(_, zone) | Action::Battle(_, zone, ..) => [zone, zone],
+            Action::March(_, from, to, _) => [from, to],
+            Action::StartNegotiation | Action::EndNegotiation(_) => return None,
+        };
+        zones.into_iter().find(|&zone| !self.layout.contains(zone))
     }
 
     pub(crate) fn process_action(self, action: Action) -> TurnResult {
+        if self.is_over() {
+            return TurnResult(
+                self,
+                Err(Error {
+                    kind: ErrorKind::GameOver,
+                    action,
+                }),
+            );
+        }
+
         if self.current_negotiation && !matches!(action, Action::EndNegotiation(_)) {
             return TurnResult(
                 self,
                 Err(Error {
-                    reason: Error::NEGOTIATION_IN_PROGRESS,
+                    kind: ErrorKind::NegotiationInProgress,
+                    action,
+                }),
+            );
+        }
+
+        if let Some(zone) = self.unknown_zone(action) {
+            return TurnResult(
+                self,
+                Err(Error {
+                    kind: ErrorKind::UnknownZone(zone),
                     action,
                 }),
             );
@@ -313,10 +585,6 @@ impl Board {
         let mut next = self;
         let player = self.next_player;
 
-        if !matches!(action, Action::StartNegotiation) {
-            next.advance_turn();
-        }
-
         let res = match action {
             Action::EndNegotiation(crew) => next.end_negotiation(player, crew),
             Action::Battle(crew, zone, red, blue, green) => {
@@ -325,16 +593,662 @@ impl Board {
             Action::March(crew, from, to, amount) => next.march(player, crew, from, to, amount),
             Action::StartNegotiation => next.start_negotiation(player),
             Action::Recruit(crew, zone) => next.recruit(player, crew, zone),
+        };
+
+        if let Err(kind) = res {
+            return TurnResult(self, Err(Error { action, kind }));
         }
-        .map_err(|reason| Error { action, reason })
-        .map(|_| {
-            if next.consecutive_negotiations >= next.num_players {
-                Some(next.score().map_or(Winner::Draw, Winner::Player))
-            } else {
-                None
+
+        if !matches!(action, Action::StartNegotiation) {
+            next.advance_turn();
+        }
+        debug_assert!(
+            self.check_invariants().is_err() || next.check_invariants().is_ok(),
+            "{action} broke the board: {:?}",
+            next.check_invariants()
+        );
+
+        let winner = if next.is_over() {
+            Some(next.score().map_or(Winner::Draw, Winner::Player))
+        } else {
+            None
+        };
+
+        TurnResult(next, Ok(winner))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::GameEnd;
+
+    mod build {
+        use super::*;
+
+        #[test]
+        fn same_seed_gives_same_board() {
+            assert_eq!(Board::build(3, 42), Board::build(3, 42));
+        }
+
+        #[test]
+        fn different_seeds_give_different_boards() {
+            assert_ne!(Board::build(3, 42), Board::build(3, 43));
+        }
+
+        #[test]
+        fn deals_by_the_rules() {
+            let rules = RulesConfig {
+                hand_size: 5,
+                zone_fill: 1,
+                unused_seats: UnusedSeats::Dealt,
+                ..RulesConfig::STANDARD
+            };
+            let board = Board::build_with_rules(2, 42, rules).unwrap();
+            assert_eq!(board.hand(Player::Alpha).total(), 5);
+            assert_eq!(board.hand(Player::Epsilon).total(), 5);
+            assert_eq!(board.zone(Zone::Orange).total(), 1);
+            assert_eq!(board.bag().total(), 57 - 8 - 25);
+            assert_eq!(board.check_invariants(), Ok(()));
+            assert_eq!(board.score_report().ranking.len(), 2);
+        }
+
+        #[test]
+        fn rejects_rules_that_need_more_crew_than_the_bag_holds() {
+            let rules = RulesConfig {
+                hand_size: 10,
+                ..RulesConfig::STANDARD
+            };
+            assert_eq!(
+                Board::build_with_rules(5, 42, rules),
+                Err(SetupError::NotEnoughCrew {
+                    needed: 66,
+                    available: 57
+                })
+            );
+        }
+
+        #[test]
+        fn rejects_rules_with_more_of_a_crew_than_a_count_holds() {
+            let rules = RulesConfig {
+                bag: CrewCounts {
+                    rogues: u8::MAX,
+                    ..RulesConfig::STANDARD.bag
+                },
+                ..RulesConfig::STANDARD
+            };
+            assert!(matches!(
+                Board::build_with_rules(2, 42, rules),
+                Err(SetupError::TooManyCrew {
+                    crew: Crew::Rogues,
+                    ..
+                })
+            ));
+        }
+
+        #[test]
+        fn deals_to_the_zones_of_the_map() {
+            let map: Map = "keep home bullies : gate\n\
+                            gate : keep yard\n\
+                            yard : gate"
+                .parse()
+                .unwrap();
+            let board = Board::build_on_map(3, 42, RulesConfig::STANDARD, &map).unwrap();
+            assert_eq!(board.zones().count(), 3);
+            assert_eq!(board.zone(Zone::new(0)).get(Crew::Bullies), 2);
+            assert_eq!(board.zone(Zone::new(1)).total(), 2);
+            assert_eq!(board.zone(Zone::Cyan), CrewCounts::default());
+            assert_eq!(board.bag().total(), 57 - 4 - 24);
+            assert_eq!(board.check_invariants(), Ok(()));
+            for action in board.legal_actions() {
+                let TurnResult(_, result) = board.process_action(action);
+                assert!(result.is_ok(), "{action:?} failed");
+            }
+        }
+
+        #[test]
+        fn deals_from_the_configured_bag() {
+            let rules = RulesConfig {
+                hand_size: 3,
+                zone_fill: 1,
+                bag: CrewCounts {
+                    rogues: 12,
+                    bullies: 4,
+                    goons: 0,
+                },
+                ..RulesConfig::STANDARD
+            };
+            let board = Board::build_with_rules(2, 42, rules).unwrap();
+            assert_eq!(board.bag().total(), 16 - 8 - 6);
+            assert_eq!(board.hand(Player::Alpha).goons, 0);
+            assert_eq!(board.check_invariants(), Ok(()));
+            assert_eq!(
+                Board::build_with_rules(5, 42, rules),
+                Err(SetupError::NotEnoughCrew {
+                    needed: 23,
+                    available: 16
+                })
+            );
+        }
+
+        #[test]
+        fn rejects_a_game_that_ends_before_it_begins() {
+            let rules = RulesConfig {
+                game_end: GameEnd::Negotiations(0),
+                ..RulesConfig::STANDARD
+            };
+            assert_eq!(
+                Board::build_with_rules(3, 42, rules),
+                Err(SetupError::NoNegotiationsToEnd)
+            );
+        }
+    }
+
+    mod queries {
+        use super::*;
+
+        #[test]
+        fn active_players_lists_seated_players() {
+            let board = Board::build(3, 1).unwrap();
+            assert_eq!(
+                board.active_players().collect::<Vec<_>>(),
+                [Player::Alpha, Player::Beta, Player::Gamma]
+            );
+        }
+
+        #[test]
+        fn counts_every_crew_member() {
+            let board = Board::build(3, 1).unwrap();
+            let zones: u16 = board.zones().map(|zone| board.zone(zone).total()).sum();
+            let hands: u16 = enum_iterator::all::<Player>()
+                .map(|player| board.hand(player).total())
+                .sum();
+            assert_eq!(zones + hands + board.bag().total(), 63);
+            assert_eq!(board.hand(Player::Delta), CrewCounts::default());
+        }
+    }
+
+    mod turn_order {
+        use super::*;
+
+        const ROTATIONS: [&[Player]; 4] = [
+            &[Player::Alpha, Player::Beta],
+            &[Player::Alpha, Player::Beta, Player::Gamma],
+            &[Player::Alpha, Player::Beta, Player::Gamma, Player::Delta],
+            &[
+                Player::Alpha,
+                Player::Beta,
+                Player::Gamma,
+                Player::Delta,
+                Player::Epsilon,
+            ],
+        ];
+
+        /// Takes a turn that changes nothing but whose turn it is.
+        fn pass(board: &Board) -> Board {
+            let action = board
+                .legal_actions()
+                .into_iter()
+                .find(|action| matches!(action, Action::March(.., 0)))
+                .unwrap();
+            let TurnResult(board, result) = board.process_action(action);
+            assert_eq!(result, Ok(None));
+            board
+        }
+
+        #[test]
+        fn plays_full_rotations_at_every_player_count() {
+            for rotation in ROTATIONS {
+                let num_players = u8::try_from(rotation.len()).unwrap();
+                let mut board = Board::build(num_players, 5).unwrap();
+                assert!(board.active_players().eq(rotation.iter().copied()));
+                for &player in rotation.iter().cycle().take(rotation.len() * 2) {
+                    assert_eq!(board.next_player(), player, "{num_players} players");
+                    board = pass(&board);
+                }
+                assert_eq!(board.next_player(), Player::Alpha);
             }
-        });
+        }
+
+        #[test]
+        fn negotiations_pass_the_turn_at_every_player_count() {
+            for rotation in ROTATIONS {
+                let num_players = u8::try_from(rotation.len()).unwrap();
+                let mut board = Board::build(num_players, 5).unwrap();
+                for &player in &rotation[1..] {
+                    let TurnResult(started, _) = board.process_action(Action::StartNegotiation);
+                    let TurnResult(ended, result) =
+                        started.process_action(started.legal_actions()[0]);
+                    assert_eq!(result, Ok(None));
+                    assert_eq!(ended.next_player(), player, "{num_players} players");
+                    board = ended;
+                }
+            }
+        }
+
+        #[test]
+        fn an_unseated_next_player_still_ends() {
+            let mut board = Board::build(2, 5).unwrap();
+            board.next_player = Player::Gamma;
+            board.advance_turn();
+            assert_eq!(board.next_player(), Player::Alpha);
+
+            board.next_player = Player::Gamma;
+            for zone in [Zone::Red, Zone::Blue, Zone::Green] {
+                *board.get_space_mut(zone) = BoardSpace::new(1, 0, 0);
+            }
+            assert_eq!(board.score_report().ranking.len(), 2);
+        }
+    }
+
+    mod legal_actions {
+        use super::*;
+
+        #[test]
+        fn every_listed_action_succeeds() {
+            let board = Board::build(3, 1).unwrap();
+            for action in board.legal_actions() {
+                let TurnResult(_, result) = board.process_action(action);
+                assert!(result.is_ok(), "{action:?} failed");
+            }
+        }
+
+        #[test]
+        fn lists_battles_in_crowded_zones() {
+            let mut board = Board::build(2, 1).unwrap();
+            *board.get_space_mut(Zone::Red) = BoardSpace::new(1, 200, 200);
+            board
+                .get_hand_mut(board.next_player())
+                .add_crew(Crew::Rogues, 1);
+            let battles = board
+                .legal_actions()
+                .into_iter()
+                .filter(|action| matches!(action, Action::Battle(Crew::Rogues, Zone::Red, ..)))
+                .count();
+            assert_eq!(battles, 2);
+        }
+
+        #[test]
+        fn only_lists_end_negotiation_during_negotiation() {
+            let board = Board::build(3, 1).unwrap();
+            let TurnResult(board, _) = board.process_action(Action::StartNegotiation);
+            let actions = board.legal_actions();
+            assert!(!actions.is_empty());
+            assert!(actions
+                .iter()
+                .all(|action| matches!(action, Action::EndNegotiation(_))));
+        }
 
-        TurnResult(next, res)
+        #[test]
+        fn does_not_list_marches_between_non_adjacent_zones() {
+            let board = Board::build(3, 1).unwrap();
+            assert!(!board
+                .legal_actions()
+                .iter()
+                .any(|action| matches!(action, Action::March(_, Zone::Red, Zone::Blue, _))));
+        }
+
+        #[test]
+        fn lists_nothing_once_the_game_is_over() {
+            let board = Board {
+                consecutive_negotiations: 3,
+                ..Board::build(3, 1).unwrap()
+            };
+            assert!(board.is_over());
+            assert!(board.legal_actions().is_empty());
+        }
+    }
+
+    mod process_action {
+        use super::*;
+
+        /// Alpha holds two rogues; the home bases hold their starting crew.
+        const BOARD: Board = Board {
+            alpha: BoardSpace::home_base(Crew::Rogues),
+            ..Board::EMPTY
+        };
+
+        fn assert_rejected(board: &Board, action: Action, kind: ErrorKind) {
+            let TurnResult(after, result) = board.process_action(action);
+            assert_eq!(after, *board);
+            assert_eq!(result, Err(Error { action, kind }));
+        }
+
+        #[test]
+        fn rejects_bad_player_count() {
+            assert_eq!(Board::build(1, 0), Err(SetupError::BadPlayerCount(1)));
+            assert_eq!(Board::build(6, 0), Err(SetupError::BadPlayerCount(6)));
+        }
+
+        #[test]
+        fn rejects_march_between_non_adjacent_zones() {
+            assert_rejected(
+                &BOARD,
+                Action::March(Crew::Rogues, Zone::Red, Zone::Blue, 1),
+                ErrorKind::NotAdjacent {
+                    from: Zone::Red,
+                    to: Zone::Blue,
+                },
+            );
+        }
+
+        #[test]
+        fn rejects_zones_off_the_map() {
+            let zone = Zone::new(11);
+            assert_rejected(
+                &BOARD,
+                Action::March(Crew::Rogues, Zone::Red, zone, 1),
+                ErrorKind::UnknownZone(zone),
+            );
+            assert_rejected(
+                &BOARD,
+                Action::Recruit(Crew::Rogues, zone),
+                ErrorKind::UnknownZone(zone),
+            );
+        }
+
+        #[test]
+        fn rejects_march_of_more_crew_than_zone_holds() {
+            assert_rejected(
+                &BOARD,
+                Action::March(Crew::Rogues, Zone::Red, Zone::Orange, 3),
+                ErrorKind::NotEnoughCrew {
+                    zone: Zone::Red,
+                    crew: Crew::Rogues,
+                    needed: 3,
+                    available: 2,
+                },
+            );
+        }
+
+        #[test]
+        fn rejects_march_with_crew_missing_from_hand() {
+            assert_rejected(
+                &BOARD,
+                Action::March(Crew::Goons, Zone::Green, Zone::Cyan, 1),
+                ErrorKind::MissingFromHand {
+                    player: Player::Alpha,
+                    crew: Crew::Goons,
+                },
+            );
+        }
+
+        #[test]
+        fn rejects_recruit_with_crew_missing_from_hand() {
+            assert_rejected(
+                &BOARD,
+                Action::Recruit(Crew::Goons, Zone::Red),
+                ErrorKind::MissingFromHand {
+                    player: Player::Alpha,
+                    crew: Crew::Goons,
+                },
+            );
+        }
+
+        #[test]
+        fn rejects_battle_removing_attacking_crew() {
+            assert_rejected(
+                &BOARD,
+                Action::Battle(Crew::Rogues, Zone::Red, 1, 0, 0),
+                ErrorKind::RemovingAttackingCrew(Crew::Rogues),
+            );
+        }
+
+        #[test]
+        fn rejects_battle_removing_nothing() {
+            assert_rejected(
+                &BOARD,
+                Action::Battle(Crew::Rogues, Zone::Red, 0, 0, 0),
+                ErrorKind::NothingRemoved,
+            );
+        }
+
+        #[test]
+        fn rejects_battle_without_enough_attackers() {
+            assert_rejected(
+                &BOARD,
+                Action::Battle(Crew::Rogues, Zone::Blue, 0, 1, 0),
+                ErrorKind::NotEnoughCrew {
+                    zone: Zone::Blue,
+                    crew: Crew::Rogues,
+                    needed: 1,
+                    available: 0,
+                },
+            );
+        }
+
+        #[test]
+        fn rejects_battle_removing_absent_crew() {
+            assert_rejected(
+                &BOARD,
+                Action::Battle(Crew::Rogues, Zone::Red, 0, 1, 0),
+                ErrorKind::NotEnoughCrew {
+                    zone: Zone::Red,
+                    crew: Crew::Bullies,
+                    needed: 1,
+                    available: 0,
+                },
+            );
+        }
+
+        #[test]
+        fn rejects_actions_during_negotiation() {
+            let board = Board {
+                current_negotiation: true,
+                ..BOARD
+            };
+            assert_rejected(
+                &board,
+                Action::Recruit(Crew::Rogues, Zone::Red),
+                ErrorKind::NegotiationInProgress,
+            );
+        }
+
+        #[test]
+        fn rejects_negotiation_with_empty_bag() {
+            assert_rejected(&BOARD, Action::StartNegotiation, ErrorKind::EmptyBag);
+        }
+
+        #[test]
+        fn ends_after_the_configured_negotiations() {
+            let board = Board {
+                num_players: 4,
+                rules: RulesConfig {
+                    game_end: GameEnd::Negotiations(1),
+                    empty_bag: EmptyBagRule::NoDraw,
+                    ..RulesConfig::STANDARD
+                },
+                ..BOARD
+            };
+            let TurnResult(board, _) = board.process_action(Action::StartNegotiation);
+            let TurnResult(board, result) =
+                board.process_action(Action::EndNegotiation(Crew::Rogues));
+            assert!(board.is_over());
+            assert!(matches!(result, Ok(Some(_))));
+        }
+
+        mod no_draw {
+            use super::*;
+
+            const NO_DRAW: Board = Board {
+                rules: RulesConfig {
+                    empty_bag: EmptyBagRule::NoDraw,
+                    ..RulesConfig::STANDARD
+                },
+                ..BOARD
+            };
+
+            #[test]
+            fn negotiates_without_drawing() {
+                let TurnResult(board, result) = NO_DRAW.process_action(Action::StartNegotiation);
+                assert_eq!(result, Ok(None));
+                assert!(board.is_negotiating());
+                assert_eq!(board.alpha, NO_DRAW.alpha);
+                assert_eq!(
+                    board.legal_actions(),
+                    [Action::EndNegotiation(Crew::Rogues)]
+                );
+            }
+
+            #[test]
+            fn rejects_negotiation_with_empty_hand() {
+                let board = Board {
+                    alpha: BoardSpace::EMPTY,
+                    ..NO_DRAW
+                };
+                assert!(!board.legal_actions().contains(&Action::StartNegotiation));
+                assert_rejected(&board, Action::StartNegotiation, ErrorKind::EmptyBag);
+            }
+        }
+
+        #[test]
+        fn rejects_every_action_once_the_game_is_over() {
+            let board = Board {
+                consecutive_negotiations: 2,
+                num_players: 2,
+                ..BOARD
+            };
+            assert!(board.is_over());
+            for action in [
+                Action::StartNegotiation,
+                Action::Recruit(Crew::Rogues, Zone::Red),
+                Action::EndNegotiation(Crew::Rogues),
+            ] {
+                assert_rejected(&board, action, ErrorKind::GameOver);
+            }
+        }
+
+        #[test]
+        fn rejects_end_negotiation_without_negotiation() {
+            assert_rejected(
+                &BOARD,
+                Action::EndNegotiation(Crew::Rogues),
+                ErrorKind::NoNegotiationInProgress,
+            );
+        }
+
+        #[test]
+        fn rejects_end_negotiation_with_crew_missing_from_hand() {
+            let board = Board {
+                current_negotiation: true,
+                ..BOARD
+            };
+            assert_rejected(
+                &board,
+                Action::EndNegotiation(Crew::Goons),
+                ErrorKind::MissingFromHand {
+                    player: Player::Alpha,
+                    crew: Crew::Goons,
+                },
+            );
+        }
+
+        #[test]
+        fn same_seed_and_actions_give_same_board() {
+            let play = |seed| {
+                let board = Board::build(2, seed).unwrap();
+                let TurnResult(board, _) = board.process_action(Action::StartNegotiation);
+                board
+            };
+            assert_eq!(play(7), play(7));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    mod versioned {
+        use super::*;
+
+        #[test]
+        fn round_trips_through_json() {
+            let board = Board::build(4, 9).unwrap();
+            let json = serde_json::to_string(&board).unwrap();
+            assert_eq!(serde_json::from_str::<Board>(&json).unwrap(), board);
+        }
+
+        #[test]
+        fn rejects_unknown_version() {
+            let board = Board::build(4, 9).unwrap();
+            let mut json = serde_json::to_value(board).unwrap();
+            json["version"] = 3.into();
+            assert!(serde_json::from_value::<Board>(json).is_err());
+        }
+
+        #[test]
+        fn reads_boards_saved_without_rules() {
+            let board = Board::build(4, 9).unwrap();
+            let mut json = serde_json::to_value(board).unwrap();
+            assert!(json["board"]
+                .as_object_mut()
+                .unwrap()
+                .remove("rules")
+                .is_some());
+            assert_eq!(serde_json::from_value::<Board>(json).unwrap(), board);
+        }
+
+        #[test]
+        fn reads_rules_saved_without_a_bag() {
+            let board = Board::build(4, 9).unwrap();
+            let mut json = serde_json::to_value(board).unwrap();
+            assert!(json["board"]["rules"]
+                .as_object_mut()
+                .unwrap()
+                .remove("bag")
+                .is_some());
+            assert_eq!(serde_json::from_value::<Board>(json).unwrap(), board);
+        }
+
+        #[test]
+        fn reads_version_one_boards_onto_the_standard_map() {
+            let board = Board::build(4, 9).unwrap();
+            let mut json = serde_json::to_value(board).unwrap();
+            json["version"] = 1.into();
+            let saved = json["board"].as_object_mut().unwrap();
+            let zones = saved.remove("zones").unwrap();
+            assert!(saved.remove("layout").is_some());
+            for zone in Zone::STANDARD {
+                assert!(saved
+                    .insert(zone.to_string(), zones[zone.index()].clone())
+                    .is_none());
+            }
+            assert_eq!(serde_json::from_value::<Board>(json).unwrap(), board);
+        }
+
+        /// Changes a saved board and checks that it no longer loads.
+        fn assert_rejected(change: impl FnOnce(&mut serde_json::Value)) {
+            let mut json = serde_json::to_value(Board::build(3, 9).unwrap()).unwrap();
+            change(&mut json["board"]);
+            assert!(serde_json::from_value::<Board>(json).is_err());
+        }
+
+        #[test]
+        fn rejects_layouts_with_too_many_zones() {
+            assert_rejected(|board| board["layout"]["num_zones"] = 40.into());
+        }
+
+        #[test]
+        fn rejects_one_way_adjacencies() {
+            assert_rejected(|board| {
+                let adjacency = board["layout"]["adjacency"][0].as_u64().unwrap();
+                board["layout"]["adjacency"][0] = (adjacency ^ 1 << 10).into();
+            });
+        }
+
+        #[test]
+        fn rejects_adjacencies_and_home_bases_off_the_map() {
+            assert_rejected(|board| board["layout"]["adjacency"][0] = (1_u64 << 20).into());
+            assert_rejected(|board| board["layout"]["home_bases"][20] = "Rogues".into());
+        }
+
+        #[test]
+        fn rejects_crew_in_zones_off_the_map() {
+            assert_rejected(|board| board["zones"][20] = board["zones"][0].clone());
+        }
+
+        #[test]
+        fn rejects_player_counts_and_next_players_that_cannot_be() {
+            assert_rejected(|board| board["num_players"] = 7.into());
+            assert_rejected(|board| board["next_player"] = "Delta".into());
+            assert_rejected(|board| board["consecutive_negotiations"] = 9.into());
+        }
     }
 }
@@ -1,8 +1,9 @@
-use crate::Crew;
+use crate::{Crew, CrewCounts};
 use rand::Rng;
 
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
 #[cfg_attr(debug_assertions, derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(super) struct Bag {
     rogues: u8,
     goons: u8,
@@ -16,6 +17,22 @@ impl Bag {
         bullies: 0,
     };
 
+    pub(super) const fn new(rogues: u8, bullies: u8, goons: u8) -> Self {
+        Self {
+            rogues,
+            goons,
+            bullies,
+        }
+    }
+
+    pub(super) const fn get_crew(self, crew: Crew) -> u8 {
+        match crew {
+            Crew::Rogues => self.rogues,
+            Crew::Bullies => self.bullies,
+            Crew::Goons => self.goons,
+        }
+    }
+
     pub(super) fn from_slice(slice: &[Crew]) -> Self {
         let mut bag = Self::default();
         for crew in slice {
@@ -28,30 +45,46 @@ impl Bag {
         bag
     }
 
-    pub(super) fn draw(&mut self) -> Crew {
-        let total = self.rogues + self.goons + self.bullies;
-        let mut rng = rand::thread_rng();
+    pub(super) const fn is_empty(self) -> bool {
+        self.rogues == 0 && self.goons == 0 && self.bullies == 0
+    }
+
+    /// Takes a random crew member out of the bag, or `None` if the bag is empty.
+    pub(super) fn draw<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Option<Crew> {
+        let total = u16::from(self.rogues) + u16::from(self.goons) + u16::from(self.bullies);
+        if total == 0 {
+            return None;
+        }
         let mut roll = rng.gen_range(0..total);
-        if roll < self.rogues {
+        if roll < u16::from(self.rogues) {
             self.rogues -= 1;
-            Crew::Rogues
+            return Some(Crew::Rogues);
+        }
+        roll -= u16::from(self.rogues);
+        if roll < u16::from(self.goons) {
+            self.goons -= 1;
+            Some(Crew::Goons)
         } else {
-            roll -= self.rogues;
-            if roll < self.goons {
-                self.goons -= 1;
-                Crew::Goons
-            } else {
-                self.bullies -= 1;
-                Crew::Bullies
-            }
+            self.bullies -= 1;
+            Some(Crew::Bullies)
         }
     }
 
-    pub(super) fn replace(&mut self, crew: Crew) {
+    pub(super) const fn replace(&mut self, crew: Crew, amount: u8) {
         match crew {
-            Crew::Rogues => self.rogues += 1,
-            Crew::Goons => self.goons += 1,
-            Crew::Bullies => self.bullies += 1,
+            Crew::Rogues => self.rogues = self.rogues.saturating_add(amount),
+            Crew::Goons => self.goons = self.goons.saturating_add(amount),
+            Crew::Bullies => self.bullies = self.bullies.saturating_add(amount),
+        }
+    }
+}
+
+impl From<Bag> for CrewCounts {
+    fn from(bag: Bag) -> Self {
+        Self {
+            rogues: bag.rogues,
+            bullies: bag.bullies,
+            goons: bag.goons,
         }
     }
 }
@@ -1,11 +1,39 @@
 use std::cmp::Ordering;
 
-use crate::{Crew, Error};
+use crate::{Control, Crew, CrewCounts, DecidedBy, ErrorKind, Player, Zone};
 
-/// A space on the board, including player hands.
-#[derive(Default, Clone, Copy)]
+/// A space did not hold enough of a crew.
+#[derive(Clone, Copy)]
 #[cfg_attr(debug_assertions, derive(Debug))]
 #[cfg_attr(test, derive(PartialEq, Eq))]
+pub(super) struct Shortfall {
+    crew: Crew,
+    needed: u8,
+    available: u8,
+}
+
+impl Shortfall {
+    pub(super) const fn in_zone(self, zone: Zone) -> ErrorKind {
+        ErrorKind::NotEnoughCrew {
+            zone,
+            crew: self.crew,
+            needed: self.needed,
+            available: self.available,
+        }
+    }
+
+    pub(super) const fn in_hand(self, player: Player) -> ErrorKind {
+        ErrorKind::MissingFromHand {
+            player,
+            crew: self.crew,
+        }
+    }
+}
+
+/// A space on the board, including player hands.
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(super) struct BoardSpace {
     rogues: u8,
     bullies: u8,
@@ -14,7 +42,7 @@ pub(super) struct BoardSpace {
 
 impl BoardSpace {
     /// Returns the number of crew members of the given type in this space.
-    const fn get_crew(self, crew: Crew) -> u8 {
+    pub(super) const fn get_crew(self, crew: Crew) -> u8 {
         match crew {
             Crew::Rogues => self.rogues,
             Crew::Bullies => self.bullies,
@@ -28,6 +56,14 @@ impl BoardSpace {
         goons: 0,
     };
 
+    pub(super) const fn new(rogues: u8, bullies: u8, goons: u8) -> Self {
+        Self {
+            rogues,
+            bullies,
+            goons,
+        }
+    }
+
     pub(super) const fn home_base(crew: Crew) -> Self {
         match crew {
             Crew::Rogues => Self {
@@ -45,61 +81,65 @@ impl BoardSpace {
         }
     }
 
-    pub(super) fn subtract_crew(&mut self, crew: Crew, amount: u8) -> Result<(), &'static str> {
-        let crew = self.get_crew_mut(crew);
-        crew.checked_sub(amount)
-            .map_or(Err(Error::NOT_ENOUGH_STONES_IN_ZONE), |diff| {
-                *crew = diff;
-                Ok(())
-            })
+    pub(super) fn subtract_crew(&mut self, crew: Crew, amount: u8) -> Result<(), Shortfall> {
+        self.check_crew(crew, amount)?;
+        *self.get_crew_mut(crew) -= amount;
+        Ok(())
     }
 
-    pub(super) fn add_crew(&mut self, crew: Crew, amount: u8) {
-        *self.get_crew_mut(crew) += amount;
+    pub(super) const fn add_crew(&mut self, crew: Crew, amount: u8) {
+        let count = self.get_crew_mut(crew);
+        *count = count.saturating_add(amount);
     }
 
-    pub(super) const fn check_crew(self, crew: Crew, amount: u8) -> Result<(), &'static str> {
-        if self.get_crew(crew) < amount {
-            return Err(Error::NOT_ENOUGH_STONES_IN_ZONE);
+    pub(super) const fn check_crew(self, crew: Crew, amount: u8) -> Result<(), Shortfall> {
+        let available = self.get_crew(crew);
+        if available < amount {
+            return Err(Shortfall {
+                crew,
+                needed: amount,
+                available,
+            });
         }
         Ok(())
     }
 
     pub(super) fn controlling_crew(self, swords: Self, flags: Self) -> Option<Crew> {
-        let flag_sort = flags.get_sorted_crews();
-        let sword_sort = swords.get_sorted_crews();
+        self.control([(swords, DecidedBy::Swords), (flags, DecidedBy::Flags)])
+            .map(|control| control.crew)
+    }
+
+    /// Finds the crew in control, breaking ties with each tally in turn.
+    pub(super) fn control(self, tiebreaks: [(Self, DecidedBy); 2]) -> Option<Control> {
+        let decided = |crew, decided_by| Some(Control { crew, decided_by });
 
         if self.rogues == self.goons && self.rogues == self.bullies {
-            if swords.get_crew(sword_sort[0]) == swords.get_crew(sword_sort[1]) {
-                if flags.get_crew(flag_sort[0]) == flags.get_crew(flag_sort[1]) {
-                    return None;
+            for (tally, decided_by) in tiebreaks {
+                let sorted = tally.get_sorted_crews();
+                if tally.get_crew(sorted[0]) != tally.get_crew(sorted[1]) {
+                    return decided(sorted[0], decided_by);
                 }
-                return Some(flag_sort[0]);
             }
-            return Some(sword_sort[0]);
+            return None;
         }
 
         let self_sort = self.get_sorted_crews();
 
         if self.get_crew(self_sort[0]) != self.get_crew(self_sort[1]) {
-            return Some(self_sort[0]);
+            return decided(self_sort[0], DecidedBy::Majority);
         }
 
-        match swords
-            .get_crew(self_sort[0])
-            .cmp(&swords.get_crew(self_sort[1]))
-        {
-            Ordering::Greater => Some(self_sort[0]),
-            Ordering::Less => Some(self_sort[1]),
-            Ordering::Equal => match flags
+        for (tally, decided_by) in tiebreaks {
+            match tally
                 .get_crew(self_sort[0])
-                .cmp(&flags.get_crew(self_sort[1]))
+                .cmp(&tally.get_crew(self_sort[1]))
             {
-                Ordering::Greater => Some(self_sort[0]),
-                Ordering::Less => Some(self_sort[1]),
-                Ordering::Equal => None,
-            },
+                Ordering::Greater => return decided(self_sort[0], decided_by),
+                Ordering::Less => return decided(self_sort[1], decided_by),
+                Ordering::Equal => {}
+            }
         }
+        None
     }
 
     pub(super) fn loser(self) -> Option<Crew> {
@@ -111,7 +151,7 @@ impl BoardSpace {
         inverse.controlling_crew(Self::default(), Self::default())
     }
 
-    fn get_crew_mut(&mut self, crew: Crew) -> &mut u8 {
+    const fn get_crew_mut(&mut self, crew: Crew) -> &mut u8 {
         match crew {
             Crew::Rogues => &mut self.rogues,
             Crew::Bullies => &mut self.bullies,
@@ -143,6 +183,22 @@ impl BoardSpace {
     }
 }
 
+impl From<BoardSpace> for CrewCounts {
+    fn from(space: BoardSpace) -> Self {
+        Self {
+            rogues: space.rogues,
+            bullies: space.bullies,
+            goons: space.goons,
+        }
+    }
+}
+
+impl From<CrewCounts> for BoardSpace {
+    fn from(counts: CrewCounts) -> Self {
+        Self::new(counts.rogues, counts.bullies, counts.goons)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -165,7 +221,11 @@ mod test {
         let mut space = BoardSpace::home_base(Crew::Rogues);
         assert_eq!(
             space.subtract_crew(Crew::Rogues, 3),
-            Err(Error::NOT_ENOUGH_STONES_IN_ZONE)
+            Err(Shortfall {
+                crew: Crew::Rogues,
+                needed: 3,
+                available: 2,
+            })
         );
     }
 
@@ -193,7 +253,11 @@ mod test {
         let space = BoardSpace::home_base(Crew::Rogues);
         assert_eq!(
             space.check_crew(Crew::Rogues, 3),
-            Err(Error::NOT_ENOUGH_STONES_IN_ZONE)
+            Err(Shortfall {
+                crew: Crew::Rogues,
+                needed: 3,
+                available: 2,
+            })
         );
     }
 
@@ -279,7 +343,10 @@ mod test {
                             bullies: 1,
                             goons: 1,
                         };
-                        assert_eq!(space.controlling_crew(BoardSpace::EMPTY, BoardSpace::EMPTY), None);
+                        assert_eq!(
+                            space.controlling_crew(BoardSpace::EMPTY, BoardSpace::EMPTY),
+                            None
+                        );
                     }
                 }
             }
@@ -1,12 +1,4 @@
-use crate::{Crew, Zone};
-
-// 57 start in the bag, 6 start on the board
-#[rustfmt::skip]
-pub(super) const DEFAULT_BAG: [Crew; 57] = [
-    Crew::Bullies, Crew::Bullies, Crew::Bullies, Crew::Bullies, Crew::Bullies, Crew::Bullies, Crew::Bullies, Crew::Bullies, Crew::Bullies, Crew::Bullies, Crew::Bullies, Crew::Bullies, Crew::Bullies, Crew::Bullies, Crew::Bullies, Crew::Bullies, Crew::Bullies, Crew::Bullies, Crew::Bullies,
-    Crew::Goons, Crew::Goons, Crew::Goons, Crew::Goons, Crew::Goons, Crew::Goons, Crew::Goons, Crew::Goons, Crew::Goons, Crew::Goons, Crew::Goons, Crew::Goons, Crew::Goons, Crew::Goons, Crew::Goons, Crew::Goons, Crew::Goons, Crew::Goons, Crew::Goons,
-    Crew::Rogues, Crew::Rogues, Crew::Rogues, Crew::Rogues, Crew::Rogues, Crew::Rogues, Crew::Rogues, Crew::Rogues, Crew::Rogues, Crew::Rogues, Crew::Rogues, Crew::Rogues, Crew::Rogues, Crew::Rogues, Crew::Rogues, Crew::Rogues, Crew::Rogues, Crew::Rogues, Crew::Rogues,
-];
+use crate::Zone;
 
 #[rustfmt::skip]
 pub(super) const ADJACENCIES: [(Zone, Zone); 34] = [
@@ -22,5 +14,3 @@ pub(super) const ADJACENCIES: [(Zone, Zone); 34] = [
     (Zone::Cyan   , Zone::Green  ), (Zone::Cyan   , Zone::Magenta), (Zone::Cyan   , Zone::Orange ), (Zone::Cyan   , Zone::Yellow ),
     (Zone::Purple , Zone::Black  ), (Zone::Purple , Zone::Blue   ), (Zone::Purple , Zone::Gray   ), (Zone::Purple , Zone::Magenta), (Zone::Purple , Zone::White  ),
 ];
-
-pub(super) const ZONES_TO_FILL: usize = 8;
\ No newline at end of file
@@ -0,0 +1,219 @@
+use super::{Bag, Board};
+use crate::{Crew, InvariantError, Player, UnusedSeats};
+
+impl Board {
+    /// Checks that the board is consistent.
+    ///
+    /// Every crew member the game is played with must be in a zone, a hand, the bag
+    /// or a tally, with none missing and none extra. The next player must be seated,
+    /// players that are not seated must hold nothing, and the negotiation state must
+    /// fit the number of players.
+    ///
+    /// Positions made up by hand, such as those read with [`Board::from_notation`],
+    /// need not hold every crew member and can fail this check while still being playable.
+    ///
+    /// # Errors
+    /// Returns the first [`InvariantError`] found.
+    pub fn check_invariants(&self) -> Result<(), InvariantError> {
+        self.check_structure()?;
+        for crew in enum_iterator::all::<Crew>() {
+            let expected = self.pieces_in_play(crew);
+            let found = self.count_pieces(crew);
+            if found != expected {
+                return Err(InvariantError::PieceCount {
+                    crew,
+                    expected,
+                    found,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks every invariant but the piece count, which positions made up by hand may break.
+    pub(super) fn check_structure(&self) -> Result<(), InvariantError> {
+        if !(2..=5).contains(&self.num_players) {
+            return Err(InvariantError::PlayerCount(self.num_players));
+        }
+        if !self
+            .active_players()
+            .any(|player| player == self.next_player)
+        {
+            return Err(InvariantError::UnseatedNextPlayer(self.next_player));
+        }
+        if let Some(player) = enum_iterator::all::<Player>()
+            .skip(self.num_players.into())
+            .find(|&player| self.hand(player).total() > 0)
+            .filter(|_| self.rules.unused_seats == UnusedSeats::Empty)
+        {
+            return Err(InvariantError::UnseatedHand(player));
+        }
+        if self.consecutive_negotiations > self.rules.negotiations_to_end(self.num_players) {
+            return Err(InvariantError::ConsecutiveNegotiations(
+                self.consecutive_negotiations,
+            ));
+        }
+        if self.current_negotiation && self.is_over() {
+            return Err(InvariantError::NegotiationAfterGameEnd);
+        }
+        if let Some((crew, count)) = self.too_many_crew() {
+            return Err(InvariantError::TooManyCrew { crew, count });
+        }
+        Ok(())
+    }
+
+    /// A crew the game is played with more members of than a count can hold, if any,
+    /// and how many members that is.
+    pub(super) fn too_many_crew(&self) -> Option<(Crew, u16)> {
+        enum_iterator::all::<Crew>()
+            .map(|crew| (crew, self.pieces_in_play(crew)))
+            .find(|&(_, count)| count > u8::MAX.into())
+    }
+
+    /// The number of members of a crew the game is played with:
+    /// those in the bag the rules start with and those in the home bases.
+    pub(super) fn pieces_in_play(&self, crew: Crew) -> u16 {
+        Self {
+            bag: Bag::new(
+                self.rules.bag.rogues,
+                self.rules.bag.bullies,
+                self.rules.bag.goons,
+            ),
+            zones: self.layout.starting_spaces(),
+            layout: self.layout,
+            ..Self::EMPTY
+        }
+        .count_pieces(crew)
+    }
+
+    /// Counts the members of a crew wherever they are on the board.
+    pub(super) fn count_pieces(&self, crew: Crew) -> u16 {
+        let zones = self.zones().map(|zone| *self.get_space(zone));
+        let hands = enum_iterator::all::<Player>().map(|player| *self.get_hand(player));
+        zones
+            .chain(hands)
+            .chain([self.swords, self.flags])
+            .map(|space| u16::from(space.get_crew(crew)))
+            .sum::<u16>()
+            + u16::from(self.bag.get_crew(crew))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::BoardSpace;
+    use crate::{Action, TurnResult, Zone};
+
+    #[test]
+    fn accepts_new_games() {
+        for num_players in 2..=5 {
+            assert_eq!(
+                Board::build(num_players, 12).unwrap().check_invariants(),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn holds_through_a_played_game() {
+        let mut board = Board::build(4, 12).unwrap();
+        for turn in 0..200 {
+            let actions = board.legal_actions();
+            let Some(&action) = actions.get(turn * 7 % actions.len().max(1)) else {
+                break;
+            };
+            let TurnResult(next, result) = board.process_action(action);
+            assert_eq!(next.check_invariants(), Ok(()), "{action} on turn {turn}");
+            board = next;
+            if result != Ok(None) {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn holds_after_a_battle() {
+        let board = Board {
+            alpha: BoardSpace::new(0, 0, 1),
+            bag: Bag::new(19, 19, 17),
+            ..Board::EMPTY
+        }
+        .with_zone(Zone::Red, BoardSpace::new(2, 0, 1));
+        assert_eq!(board.check_invariants(), Ok(()));
+        let TurnResult(board, result) =
+            board.process_action(Action::Battle(Crew::Goons, Zone::Red, 1, 0, 0));
+        assert_eq!(result, Ok(None));
+        assert_eq!(board.check_invariants(), Ok(()));
+        assert_eq!(board.bag.get_crew(Crew::Bullies), 19);
+    }
+
+    #[test]
+    fn finds_missing_pieces() {
+        let board = Board {
+            bag: Bag::EMPTY,
+            ..Board::build(3, 12).unwrap()
+        };
+        assert!(matches!(
+            board.check_invariants(),
+            Err(InvariantError::PieceCount { expected: 21, .. })
+        ));
+    }
+
+    #[test]
+    fn finds_unseated_hands_and_players() {
+        let board = Board::build(2, 12).unwrap();
+        let with_hand = Board {
+            gamma: board.alpha,
+            alpha: BoardSpace::EMPTY,
+            ..board
+        };
+        assert_eq!(
+            with_hand.check_invariants(),
+            Err(InvariantError::UnseatedHand(Player::Gamma))
+        );
+        let with_player = Board {
+            next_player: Player::Delta,
+            ..board
+        };
+        assert_eq!(
+            with_player.check_invariants(),
+            Err(InvariantError::UnseatedNextPlayer(Player::Delta))
+        );
+    }
+
+    #[test]
+    fn finds_crews_too_large_to_count() {
+        let mut board = Board::build(2, 12).unwrap();
+        board.rules.bag.goons = u8::MAX;
+        assert!(matches!(
+            board.check_invariants(),
+            Err(InvariantError::TooManyCrew {
+                crew: Crew::Goons,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn finds_bad_negotiation_state() {
+        let board = Board::build(2, 12).unwrap();
+        let too_many = Board {
+            consecutive_negotiations: 3,
+            ..board
+        };
+        assert_eq!(
+            too_many.check_invariants(),
+            Err(InvariantError::ConsecutiveNegotiations(3))
+        );
+        let after_end = Board {
+            consecutive_negotiations: 2,
+            current_negotiation: true,
+            ..board
+        };
+        assert_eq!(
+            after_end.check_invariants(),
+            Err(InvariantError::NegotiationAfterGameEnd)
+        );
+    }
+}
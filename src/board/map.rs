@@ -0,0 +1,629 @@
+//! Maps: the zones of the board and which of them are adjacent.
+//!
+//! A map is written as text with one line per zone: its name, `home` and a crew
+//! if it is a home base, then `:` and the names of the zones adjacent to it.
+//! Blank lines and lines starting with `#` are skipped.
+//!
+//! ```text
+//! castle home rogues : bridge
+//! bridge : castle tower
+//! tower home goons : bridge
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use super::{BoardSpace, ADJACENCIES};
+use crate::{Crew, MapError, Zone};
+
+/// The most zones a map can have.
+pub(super) const MAX_ZONES: usize = 32;
+
+/// The part of a [`Map`] that play depends on,
+/// small enough to keep in every [`Board`](super::Board).
+///
+/// A deserialized layout is checked as [`Layout::new`] checks it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "UncheckedLayout")
+)]
+pub(super) struct Layout {
+    num_zones: u8,
+    /// For each zone, a bit set for each zone adjacent to it.
+    adjacency: [u32; MAX_ZONES],
+    home_bases: [Option<Crew>; MAX_ZONES],
+}
+
+/// A [`Layout`] as it is deserialized, before it is checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedLayout {
+    num_zones: u8,
+    adjacency: [u32; MAX_ZONES],
+    home_bases: [Option<Crew>; MAX_ZONES],
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedLayout> for Layout {
+    type Error = MapError;
+
+    fn try_from(unchecked: UncheckedLayout) -> Result<Self, Self::Error> {
+        let num_zones = usize::from(unchecked.num_zones);
+        if !(1..=MAX_ZONES).contains(&num_zones) {
+            return Err(MapError::ZoneCount(num_zones));
+        }
+        let indices = (0..).map(Zone::new).take(MAX_ZONES);
+        if let Some(zone) = indices
+            .clone()
+            .skip(num_zones)
+            .find(|zone| unchecked.home_bases[zone.index()].is_some())
+        {
+            return Err(MapError::UnknownZone(zone));
+        }
+        let adjacencies: Vec<(Zone, Zone)> = indices
+            .clone()
+            .flat_map(|from| {
+                let adjacent = unchecked.adjacency[from.index()];
+                indices
+                    .clone()
+                    .filter(move |to| adjacent >> to.index() & 1 == 1)
+                    .map(move |to| (from, to))
+            })
+            .collect();
+        Self::new(&unchecked.home_bases[..num_zones], &adjacencies)
+    }
+}
+
+impl Layout {
+    pub(super) const STANDARD: Self = {
+        let mut layout = Self {
+            num_zones: 11,
+            adjacency: [0; MAX_ZONES],
+            home_bases: [None; MAX_ZONES],
+        };
+        layout.home_bases[Zone::Red.index()] = Some(Crew::Rogues);
+        layout.home_bases[Zone::Blue.index()] = Some(Crew::Bullies);
+        layout.home_bases[Zone::Green.index()] = Some(Crew::Goons);
+        let mut i = 0;
+        while i < ADJACENCIES.len() {
+            let (from, to) = ADJACENCIES[i];
+            layout.adjacency[from.index()] |= 1 << to.index();
+            i += 1;
+        }
+        let mut from = 0;
+        while from < MAX_ZONES {
+            let mut to = 0;
+            while to < MAX_ZONES {
+                let there = layout.adjacency[from] >> to & 1;
+                let back = layout.adjacency[to] >> from & 1;
+                assert!(there == back, "ADJACENCIES must list every pair both ways");
+                to += 1;
+            }
+            assert!(
+                layout.adjacency[from] >> from & 1 == 0,
+                "a zone is not adjacent to itself"
+            );
+            from += 1;
+        }
+        layout
+    };
+
+    /// Builds a layout, checking that every adjacency is on the map and goes both ways.
+    pub(super) fn new(
+        home_bases: &[Option<Crew>],
+        adjacencies: &[(Zone, Zone)],
+    ) -> Result<Self, MapError> {
+        if !(1..=MAX_ZONES).contains(&home_bases.len()) {
+            return Err(MapError::ZoneCount(home_bases.len()));
+        }
+        let mut layout = Self {
+            num_zones: u8::try_from(home_bases.len()).unwrap_or(u8::MAX),
+            adjacency: [0; MAX_ZONES],
+            home_bases: [None; MAX_ZONES],
+        };
+        layout.home_bases[..home_bases.len()].copy_from_slice(home_bases);
+
+        for &(from, to) in adjacencies {
+            for zone in [from, to] {
+                if !layout.contains(zone) {
+                    return Err(MapError::UnknownZone(zone));
+                }
+            }
+            if from == to {
+                return Err(MapError::SelfAdjacent(from));
+            }
+            layout.adjacency[from.index()] |= 1 << to.index();
+        }
+        for from in layout.zones() {
+            if let Some(to) = layout
+                .neighbors(from)
+                .find(|&to| !layout.is_adjacent(to, from))
+            {
+                return Err(MapError::Asymmetric { from, to });
+            }
+        }
+        Ok(layout)
+    }
+
+    pub(super) fn num_zones(self) -> usize {
+        self.num_zones.into()
+    }
+
+    pub(super) fn zones(self) -> impl Iterator<Item = Zone> + Clone {
+        (0..self.num_zones).map(Zone::new)
+    }
+
+    pub(super) const fn contains(self, zone: Zone) -> bool {
+        zone.index() < self.num_zones as usize
+    }
+
+    pub(super) const fn is_adjacent(self, from: Zone, to: Zone) -> bool {
+        self.contains(from)
+            && self.contains(to)
+            && self.adjacency[from.index()] >> to.index() & 1 == 1
+    }
+
+    pub(super) fn neighbors(self, zone: Zone) -> impl Iterator<Item = Zone> + Clone {
+        self.zones().filter(move |&to| self.is_adjacent(zone, to))
+    }
+
+    /// The zones adjacent to any of the zones in the set, as a set of the same kind.
+    fn spread(self, zones: u32) -> u32 {
+        self.zones()
+            .filter(|zone| zones >> zone.index() & 1 == 1)
+            .fold(0, |spread, zone| spread | self.adjacency[zone.index()])
+    }
+
+    pub(super) fn distance(self, from: Zone, to: Zone) -> Option<u8> {
+        if !self.contains(from) || !self.contains(to) {
+            return None;
+        }
+        let mut reached = 1 << from.index();
+        let mut frontier = reached;
+        let mut steps = 0;
+        while reached >> to.index() & 1 == 0 {
+            frontier = self.spread(frontier) & !reached;
+            if frontier == 0 {
+                return None;
+            }
+            reached |= frontier;
+            steps += 1;
+        }
+        Some(steps)
+    }
+
+    pub(super) fn components(self) -> Vec<Vec<Zone>> {
+        let mut components = Vec::new();
+        let mut seen = 0_u32;
+        for start in self.zones() {
+            if seen >> start.index() & 1 == 1 {
+                continue;
+            }
+            let mut reached = 1 << start.index();
+            loop {
+                let grown = reached | self.spread(reached);
+                if grown == reached {
+                    break;
+                }
+                reached = grown;
+            }
+            seen |= reached;
+            components.push(
+                self.zones()
+                    .filter(|zone| reached >> zone.index() & 1 == 1)
+                    .collect(),
+            );
+        }
+        components
+    }
+
+    pub(super) const fn home_base(self, zone: Zone) -> Option<Crew> {
+        if self.contains(zone) {
+            self.home_bases[zone.index()]
+        } else {
+            None
+        }
+    }
+
+    /// The zones as they are before any crew members are dealt: empty but for the home bases.
+    pub(super) const fn starting_spaces(self) -> [BoardSpace; MAX_ZONES] {
+        let mut spaces = [BoardSpace::EMPTY; MAX_ZONES];
+        let mut i = 0;
+        while i < MAX_ZONES {
+            if let Some(crew) = self.home_bases[i] {
+                spaces[i] = BoardSpace::home_base(crew);
+            }
+            i += 1;
+        }
+        spaces
+    }
+}
+
+/// What a zone of a [`Map`] is called and which crew, if any, has its home base there.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ZoneSpec {
+    /// The name of the zone, unique on the map ignoring case.
+    ///
+    /// A name is a single word without `:`, `/`, `.` or `->`,
+    /// does not start with `#` and is not a number.
+    pub name: String,
+    /// The crew that starts with two members in the zone, if any.
+    pub home_base: Option<Crew>,
+}
+
+/// The zones of a board, their names and home bases, and which of them are adjacent.
+///
+/// [`Map::standard`] is the map of the published game.
+/// Other maps can be built with [`Map::new`] or read from text, see [`Map::from_str`].
+/// A map has at most 32 zones, and the [`Zone`] with index `i` is the `i`th zone listed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct Map {
+    names: Vec<String>,
+    layout: Layout,
+}
+
+impl Map {
+    /// Builds a map from its zones and the pairs of zones that are adjacent.
+    ///
+    /// Every adjacency must be listed both ways, `(a, b)` as well as `(b, a)`.
+    ///
+    /// # Errors
+    /// Returns a [`MapError`] if there are no zones or more than 32,
+    /// if a name is invalid or used twice, or if an adjacency is not on the map,
+    /// joins a zone to itself or is not listed both ways.
+    pub fn new(zones: Vec<ZoneSpec>, adjacencies: &[(Zone, Zone)]) -> Result<Self, MapError> {
+        let home_bases: Vec<Option<Crew>> = zones.iter().map(|zone| zone.home_base).collect();
+        let layout = Layout::new(&home_bases, adjacencies)?;
+
+        let mut names: Vec<String> = Vec::with_capacity(zones.len());
+        for ZoneSpec { name, .. } in zones {
+            if !is_valid_name(&name) {
+                return Err(MapError::InvalidName(name));
+            }
+            if names.iter().any(|taken| taken.eq_ignore_ascii_case(&name)) {
+                return Err(MapError::DuplicateName(name));
+            }
+            names.push(name);
+        }
+        Ok(Self { names, layout })
+    }
+
+    /// The map of the published game, with the zones of [`Zone::STANDARD`].
+    #[must_use]
+    pub fn standard() -> Self {
+        Self {
+            names: Zone::STANDARD.iter().map(ToString::to_string).collect(),
+            layout: Layout::STANDARD,
+        }
+    }
+
+    /// A map for a layout whose zone names are not known: the standard map for
+    /// the standard layout, and otherwise the layout with its zones named by index,
+    /// as in `zone12`.
+    pub(super) fn for_layout(layout: Layout) -> Self {
+        if layout == Layout::STANDARD {
+            return Self::standard();
+        }
+        Self {
+            names: layout
+                .zones()
+                .map(|zone| format!("zone{}", zone.index()))
+                .collect(),
+            layout,
+        }
+    }
+
+    pub(super) const fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Returns the number of zones on the map.
+    #[must_use]
+    pub fn num_zones(&self) -> usize {
+        self.layout.num_zones()
+    }
+
+    /// Returns every zone on the map, in order.
+    pub fn zones(&self) -> impl Iterator<Item = Zone> + Clone {
+        self.layout.zones()
+    }
+
+    /// Returns the name of a zone, or `None` if it is not on the map.
+    #[must_use]
+    pub fn name(&self, zone: Zone) -> Option<&str> {
+        self.names.get(zone.index()).map(String::as_str)
+    }
+
+    /// Returns the zone with the given name, ignoring case.
+    #[must_use]
+    pub fn zone_named(&self, name: &str) -> Option<Zone> {
+        self.zones()
+            .find(|&zone| self.names[zone.index()].eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the crew whose home base is the zone, if any.
+    #[must_use]
+    pub const fn home_base(&self, zone: Zone) -> Option<Crew> {
+        self.layout.home_base(zone)
+    }
+
+    /// Returns whether crew members can march from one zone to the other.
+    #[must_use]
+    pub const fn is_adjacent(&self, from: Zone, to: Zone) -> bool {
+        self.layout.is_adjacent(from, to)
+    }
+
+    /// Returns the zones adjacent to a zone, in order.
+    pub fn neighbors(&self, zone: Zone) -> impl Iterator<Item = Zone> + Clone {
+        self.layout.neighbors(zone)
+    }
+
+    /// Returns the fewest marches it takes to get from one zone to the other,
+    /// or `None` if there is no way between them.
+    #[must_use]
+    pub fn distance(&self, from: Zone, to: Zone) -> Option<u8> {
+        self.layout.distance(from, to)
+    }
+
+    /// Returns the groups of zones that can be marched between, each in order.
+    ///
+    /// A map whose zones are all connected has a single group.
+    #[must_use]
+    pub fn connected_components(&self) -> Vec<Vec<Zone>> {
+        self.layout.components()
+    }
+}
+
+/// Adjacency on the standard map. Boards on other maps answer the same questions
+/// through [`Board::neighbors`](super::Board::neighbors) and [`Map`].
+impl Zone {
+    /// Returns the zones adjacent to this one on the standard map, in order.
+    pub fn neighbors(self) -> impl Iterator<Item = Self> + Clone {
+        Layout::STANDARD.neighbors(self)
+    }
+
+    /// Returns whether crew members can march between this zone and the other
+    /// on the standard map.
+    #[must_use]
+    pub const fn is_adjacent(self, other: Self) -> bool {
+        Layout::STANDARD.is_adjacent(self, other)
+    }
+
+    /// Returns the fewest marches it takes to get from this zone to the other
+    /// on the standard map, or `None` if either is not on it.
+    #[must_use]
+    pub fn distance(self, other: Self) -> Option<u8> {
+        Layout::STANDARD.distance(self, other)
+    }
+}
+
+/// Whether a zone name can be written in a map, an action and a record and read back:
+/// a single word without `:`, `/`, `.` or `->`, not starting with `#` and not a number.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.contains(|c: char| c.is_whitespace() || matches!(c, ':' | '/' | '.'))
+        && !name.contains("->")
+        && !name.starts_with('#')
+        && name.parse::<i128>().is_err()
+}
+
+impl Default for Map {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl fmt::Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for zone in self.zones() {
+            f.write_str(&self.names[zone.index()])?;
+            if let Some(crew) = self.home_base(zone) {
+                write!(f, " home {crew}")?;
+            }
+            f.write_str(" :")?;
+            for neighbor in self.layout.neighbors(zone) {
+                write!(f, " {}", self.names[neighbor.index()])?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Map {
+    type Err = MapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .collect();
+
+        let mut zones = Vec::with_capacity(lines.len());
+        let mut neighbors = Vec::with_capacity(lines.len());
+        for line in &lines {
+            let invalid = || MapError::InvalidLine((*line).to_owned());
+            let (zone, adjacent) = line.split_once(':').ok_or_else(invalid)?;
+            let (name, home_base) = match *zone.split_whitespace().collect::<Vec<_>>() {
+                [name] => (name, None),
+                [name, "home", crew] => (name, Some(crew.parse().map_err(|_| invalid())?)),
+                _ => return Err(invalid()),
+            };
+            zones.push(ZoneSpec {
+                name: name.to_owned(),
+                home_base,
+            });
+            neighbors.push(adjacent);
+        }
+
+        let find = |name: &str| {
+            zones
+                .iter()
+                .position(|zone| zone.name.eq_ignore_ascii_case(name))
+                .and_then(|index| u8::try_from(index).ok())
+                .map(Zone::new)
+                .ok_or_else(|| MapError::UnknownName(name.to_owned()))
+        };
+        let mut adjacencies = Vec::new();
+        for (from, adjacent) in neighbors.iter().enumerate() {
+            let from = find(&zones[from].name)?;
+            for to in adjacent.split_whitespace() {
+                adjacencies.push((from, find(to)?));
+            }
+        }
+
+        Self::new(zones, &adjacencies)
+    }
+}
+
+impl TryFrom<String> for Map {
+    type Error = MapError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Map> for String {
+    fn from(map: Map) -> Self {
+        map.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TRIANGLE: &str = "castle home rogues : bridge\n\
+                            bridge : castle tower\n\
+                            tower home goons : bridge\n";
+
+    #[test]
+    fn standard_map_matches_the_standard_zones() {
+        let map = Map::standard();
+        assert_eq!(map.num_zones(), 11);
+        assert_eq!(map.name(Zone::Cyan), Some("cyan"));
+        assert_eq!(map.zone_named("Purple"), Some(Zone::Purple));
+        assert_eq!(map.home_base(Zone::Blue), Some(Crew::Bullies));
+        assert!(map.is_adjacent(Zone::Red, Zone::Orange));
+        assert!(!map.is_adjacent(Zone::Red, Zone::Blue));
+    }
+
+    #[test]
+    fn standard_map_round_trips_through_text() {
+        let map = Map::standard();
+        assert_eq!(map.to_string().parse(), Ok(map));
+    }
+
+    #[test]
+    fn reads_a_map_from_text() {
+        let map: Map = TRIANGLE.parse().unwrap();
+        assert_eq!(map.num_zones(), 3);
+        assert_eq!(map.home_base(Zone::new(2)), Some(Crew::Goons));
+        assert!(map.is_adjacent(Zone::new(1), Zone::new(2)));
+        assert!(!map.is_adjacent(Zone::new(0), Zone::new(2)));
+        assert_eq!(map.to_string(), TRIANGLE);
+    }
+
+    #[test]
+    fn names_close_to_notation_round_trip() {
+        let map: Map = "a-b : b#\nb# : a-b x12\nx12 : b#".parse().unwrap();
+        assert_eq!(map.to_string().parse(), Ok(map.clone()));
+        for (from, to) in [(0, 1), (1, 0), (1, 2), (2, 1)] {
+            let action = crate::Action::March(Crew::Goons, Zone::new(from), Zone::new(to), 1);
+            let text = map.display_action(action).to_string();
+            assert_eq!(map.parse_action(&text), Ok(action), "{text}");
+        }
+    }
+
+    #[test]
+    fn finds_neighbors_on_the_standard_map() {
+        assert!(Zone::Red.neighbors().eq([Zone::Orange, Zone::Gray]));
+        assert!(Zone::Cyan.is_adjacent(Zone::Magenta));
+        assert!(!Zone::Cyan.is_adjacent(Zone::Cyan));
+        assert!(!Zone::Red.is_adjacent(Zone::new(11)));
+        for from in Zone::STANDARD {
+            for to in from.neighbors() {
+                assert!(to.is_adjacent(from), "{to} is not adjacent to {from}");
+            }
+        }
+    }
+
+    #[test]
+    fn measures_distance_in_marches() {
+        assert_eq!(Zone::Red.distance(Zone::Red), Some(0));
+        assert_eq!(Zone::Red.distance(Zone::Orange), Some(1));
+        assert_eq!(Zone::Red.distance(Zone::Blue), Some(3));
+        assert_eq!(Zone::Red.distance(Zone::new(11)), None);
+        assert_eq!(Map::standard().connected_components().len(), 1);
+    }
+
+    #[test]
+    fn finds_islands() {
+        let map: Map = "castle : bridge\nbridge : castle\nisle :".parse().unwrap();
+        assert_eq!(
+            map.connected_components(),
+            [vec![Zone::new(0), Zone::new(1)], vec![Zone::new(2)]]
+        );
+        assert_eq!(map.distance(Zone::new(0), Zone::new(2)), None);
+        assert!(map.neighbors(Zone::new(2)).next().is_none());
+    }
+
+    #[test]
+    fn rejects_one_way_adjacency() {
+        assert_eq!(
+            "castle : bridge\nbridge :".parse::<Map>(),
+            Err(MapError::Asymmetric {
+                from: Zone::new(0),
+                to: Zone::new(1)
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_names() {
+        assert_eq!(
+            "castle : moat".parse::<Map>(),
+            Err(MapError::UnknownName("moat".to_owned()))
+        );
+    }
+
+    #[test]
+    fn rejects_bad_zones() {
+        let spec = |name: &str| ZoneSpec {
+            name: name.to_owned(),
+            home_base: None,
+        };
+        assert_eq!(Map::new(Vec::new(), &[]), Err(MapError::ZoneCount(0)));
+        assert_eq!(
+            Map::new(vec![spec("a"), spec("A")], &[]),
+            Err(MapError::DuplicateName("A".to_owned()))
+        );
+        assert_eq!(
+            Map::new(vec![spec("a b")], &[]),
+            Err(MapError::InvalidName("a b".to_owned()))
+        );
+        for name in ["a->b", "a/b", "a.b", "#a", "12", "-3"] {
+            assert_eq!(
+                Map::new(vec![spec(name)], &[]),
+                Err(MapError::InvalidName(name.to_owned()))
+            );
+        }
+        assert_eq!(
+            Map::new(vec![spec("a")], &[(Zone::new(0), Zone::new(0))]),
+            Err(MapError::SelfAdjacent(Zone::new(0)))
+        );
+        assert_eq!(
+            Map::new(vec![spec("a")], &[(Zone::new(0), Zone::new(1))]),
+            Err(MapError::UnknownZone(Zone::new(1)))
+        );
+    }
+}
@@ -0,0 +1,361 @@
+use std::str::FromStr;
+
+use super::{Bag, Board, BoardSpace, Layout};
+use crate::{Crew, CrewCounts, Map, NotationError, Player, RulesConfig};
+
+impl Board {
+    /// Writes the whole position as a single line of text.
+    ///
+    /// The notation has nine fields separated by spaces:
+    /// 1. The zones of the map, in order, separated by `/`.
+    /// 2. The hands of the seated players, in seat order, separated by `/`.
+    ///    If an unused seat holds crew members, the hands of all five seats are written.
+    /// 3. The bag.
+    /// 4. The swords tally.
+    /// 5. The flags tally.
+    /// 6. The number of players.
+    /// 7. The next player, `a` for [`Player::Alpha`] through `e` for [`Player::Epsilon`].
+    /// 8. `n` if a negotiation is in progress, otherwise `-`.
+    /// 9. The number of consecutive negotiations.
+    ///
+    /// Each group of crew members is written as `rogues.bullies.goons`.
+    /// The state of the random number generator, the rules and the map
+    /// are not part of the notation.
+    ///
+    /// ```text
+    /// 2.0.0/0.2.0/0.0.2/1.1.0/0.1.1/2.0.0/1.0.1/0.2.0/1.1.0/0.0.2/1.0.1 3.2.3/2.3.3 14.17.14 0.0.0 0.0.0 2 a - 0
+    /// ```
+    #[must_use]
+    pub fn to_notation(&self) -> String {
+        let zones: Vec<String> = self
+            .zones()
+            .map(|zone| write_space(*self.get_space(zone)))
+            .collect();
+        let seats = if enum_iterator::all::<Player>()
+            .skip(self.num_players.into())
+            .any(|player| self.hand(player).total() > 0)
+        {
+            enum_iterator::cardinality::<Player>()
+        } else {
+            self.num_players.into()
+        };
+        let hands: Vec<String> = enum_iterator::all::<Player>()
+            .take(seats)
+            .map(|player| write_space(*self.get_hand(player)))
+            .collect();
+        let bag = BoardSpace::new(
+            self.bag.get_crew(Crew::Rogues),
+            self.bag.get_crew(Crew::Bullies),
+            self.bag.get_crew(Crew::Goons),
+        );
+
+        format!(
+            "{} {} {} {} {} {} {} {} {}",
+            zones.join("/"),
+            hands.join("/"),
+            write_space(bag),
+            write_space(self.swords),
+            write_space(self.flags),
+            self.num_players,
+            self.next_player.letter(),
+            if self.current_negotiation { 'n' } else { '-' },
+            self.consecutive_negotiations,
+        )
+    }
+
+    /// Reads a position on the standard map written by [`Board::to_notation`].
+    ///
+    /// The random number generator of the new board is seeded with `0`,
+    /// and the board is played by [`RulesConfig::STANDARD`].
+    /// The position must pass [`Board::check_invariants`], except that it need not
+    /// hold every crew member.
+    ///
+    /// # Errors
+    /// Returns a [`NotationError`] if the text is not a valid position.
+    pub fn from_notation(notation: &str) -> Result<Self, NotationError> {
+        Self::from_notation_on_layout(notation, Layout::STANDARD, RulesConfig::STANDARD)
+    }
+
+    /// Reads a position on the given map written by [`Board::to_notation`].
+    ///
+    /// # Errors
+    /// Returns a [`NotationError`] if the text is not a valid position on the map.
+    pub fn from_notation_on_map(notation: &str, map: &Map) -> Result<Self, NotationError> {
+        Self::from_notation_on_layout(notation, map.layout(), RulesConfig::STANDARD)
+    }
+
+    /// Reads a position on the given map, played by the given rules,
+    /// written by [`Board::to_notation`].
+    ///
+    /// # Errors
+    /// Returns a [`NotationError`] if the text is not a valid position on the map
+    /// under the rules.
+    pub fn from_notation_with_rules(
+        notation: &str,
+        map: &Map,
+        rules: RulesConfig,
+    ) -> Result<Self, NotationError> {
+        Self::from_notation_on_layout(notation, map.layout(), rules)
+    }
+
+    fn from_notation_on_layout(
+        notation: &str,
+        layout: Layout,
+        rules: RulesConfig,
+    ) -> Result<Self, NotationError> {
+        let fields: Vec<&str> = notation.split_whitespace().collect();
+        let &[zones, hands, bag, swords, flags, num_players, next_player, negotiation, consecutive] =
+            fields.as_slice()
+        else {
+            return Err(NotationError::FieldCount {
+                expected: 9,
+                found: fields.len(),
+            });
+        };
+
+        let num_players: u8 = NotationError::parse_number("number of players", num_players)?;
+        if !(2..=5).contains(&num_players) {
+            return Err(NotationError::invalid(
+                "number of players",
+                &num_players.to_string(),
+            ));
+        }
+        let mut board = Self {
+            zones: layout.starting_spaces(),
+            layout,
+            num_players,
+            rules,
+            ..Self::EMPTY
+        };
+
+        let zones: Vec<&str> = zones.split('/').collect();
+        if zones.len() != layout.num_zones() {
+            return Err(NotationError::ZoneCount {
+                expected: layout.num_zones(),
+                found: zones.len(),
+            });
+        }
+        for (zone, space) in layout.zones().zip(zones) {
+            *board.get_space_mut(zone) = parse_space("zone", space)?;
+        }
+
+        let hands: Vec<&str> = hands.split('/').collect();
+        if hands.len() != usize::from(num_players)
+            && hands.len() != enum_iterator::cardinality::<Player>()
+        {
+            return Err(NotationError::HandCount {
+                expected: num_players,
+                found: hands.len(),
+            });
+        }
+        for (player, hand) in enum_iterator::all::<Player>().zip(hands) {
+            *board.get_hand_mut(player) = parse_space("hand", hand)?;
+        }
+
+        let bag = parse_space("bag", bag)?;
+        board.bag = Bag::new(
+            bag.get_crew(Crew::Rogues),
+            bag.get_crew(Crew::Bullies),
+            bag.get_crew(Crew::Goons),
+        );
+        board.swords = parse_space("swords", swords)?;
+        board.flags = parse_space("flags", flags)?;
+
+        board.next_player = Player::from_letter(next_player)
+            .filter(|&player| {
+                enum_iterator::all::<Player>()
+                    .take(num_players.into())
+                    .any(|seated| seated == player)
+            })
+            .ok_or_else(|| NotationError::invalid("next player", next_player))?;
+        board.current_negotiation = match negotiation {
+            "n" => true,
+            "-" => false,
+            _ => return Err(NotationError::invalid("negotiation", negotiation)),
+        };
+        board.consecutive_negotiations =
+            NotationError::parse_number("consecutive negotiations", consecutive)?;
+
+        board
+            .check_structure()
+            .map_err(NotationError::InvalidPosition)?;
+        Ok(board)
+    }
+}
+
+impl FromStr for Board {
+    type Err = NotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_notation(s)
+    }
+}
+
+fn write_space(space: BoardSpace) -> String {
+    format!(
+        "{}.{}.{}",
+        space.get_crew(Crew::Rogues),
+        space.get_crew(Crew::Bullies),
+        space.get_crew(Crew::Goons)
+    )
+}
+
+fn parse_space(field: &'static str, value: &str) -> Result<BoardSpace, NotationError> {
+    CrewCounts::parse_notation(field, value).map(BoardSpace::from)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trips_a_new_game() {
+        for num_players in 2..=5 {
+            let notation = Board::build(num_players, 3).unwrap().to_notation();
+            assert_eq!(
+                Board::from_notation(&notation).unwrap().to_notation(),
+                notation
+            );
+        }
+    }
+
+    #[test]
+    fn round_trips_hands_dealt_to_unused_seats() {
+        let rules = RulesConfig {
+            unused_seats: crate::UnusedSeats::Dealt,
+            ..RulesConfig::STANDARD
+        };
+        let board = Board::build_with_rules(3, 3, rules).unwrap();
+        let notation = board.to_notation();
+        assert_eq!(
+            Board::from_notation(&notation),
+            Err(NotationError::InvalidPosition(
+                crate::InvariantError::UnseatedHand(Player::Delta)
+            ))
+        );
+        let read = Board::from_notation_with_rules(&notation, &Map::standard(), rules).unwrap();
+        assert_eq!(read.epsilon, board.epsilon);
+        assert_eq!(read.to_notation(), notation);
+    }
+
+    #[test]
+    fn rejects_positions_that_cannot_come_up() {
+        let zones = "2.0.0/0.2.0/0.0.2/1.1.0/0.1.1/2.0.0/1.0.1/0.2.0/1.1.0/0.0.2/1.0.1";
+        let read = |rest: &str| Board::from_notation(&format!("{zones} {rest}"));
+        assert_eq!(
+            read("3.2.3/2.3.3 14.17.14 0.0.0 0.0.0 2 a n 9"),
+            Err(NotationError::InvalidPosition(
+                crate::InvariantError::ConsecutiveNegotiations(9)
+            ))
+        );
+        assert_eq!(
+            read("3.2.3/2.3.3 14.17.14 0.0.0 0.0.0 2 a n 2"),
+            Err(NotationError::InvalidPosition(
+                crate::InvariantError::NegotiationAfterGameEnd
+            ))
+        );
+        assert!(read("3.2.3/2.3.3 14.17.14 0.0.0 0.0.0 2 a - 2").is_ok());
+    }
+
+    #[test]
+    fn reads_every_field() {
+        let notation = "2.0.0/0.2.0/0.0.2/1.1.0/0.1.1/2.0.0/1.0.1/0.2.0/1.1.0/0.0.2/1.0.1 \
+                        3.2.3/2.3.3/1.0.0 14.17.14 1.0.0 0.0.1 3 c n 2";
+        let board = Board::from_notation(notation).unwrap();
+        assert_eq!(board.num_players, 3);
+        assert_eq!(board.next_player, Player::Gamma);
+        assert!(board.current_negotiation);
+        assert_eq!(board.consecutive_negotiations, 2);
+        assert_eq!(
+            board.zone(crate::Zone::Cyan),
+            BoardSpace::new(2, 0, 0).into()
+        );
+        assert_eq!(board.gamma, BoardSpace::new(1, 0, 0));
+        assert_eq!(board.bag, Bag::new(14, 17, 14));
+        assert_eq!(board.flags, BoardSpace::new(0, 0, 1));
+        assert_eq!(
+            board.to_notation(),
+            notation.split_whitespace().collect::<Vec<_>>().join(" ")
+        );
+    }
+
+    #[test]
+    fn reads_positions_on_other_maps() {
+        let map: Map = "keep home rogues : yard\nyard : keep".parse().unwrap();
+        let board = Board::build_on_map(2, 3, RulesConfig::STANDARD, &map).unwrap();
+        let notation = board.to_notation();
+        let read = Board::from_notation_on_map(&notation, &map).unwrap();
+        assert_eq!(read.to_notation(), notation);
+        assert_eq!(read.check_invariants(), Ok(()));
+        assert_eq!(
+            read,
+            Board {
+                rng: read.rng,
+                ..board
+            }
+        );
+        assert_eq!(
+            Board::from_notation(&notation),
+            Err(NotationError::ZoneCount {
+                expected: 11,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_missing_fields() {
+        assert_eq!(
+            Board::from_notation("0.0.0 2 a - 0"),
+            Err(NotationError::FieldCount {
+                expected: 9,
+                found: 5
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_number_of_hands() {
+        let notation = Board::build(2, 3)
+            .unwrap()
+            .to_notation()
+            .replace(" 2 a ", " 3 a ");
+        assert_eq!(
+            Board::from_notation(&notation),
+            Err(NotationError::HandCount {
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_unseated_next_player() {
+        let notation = Board::build(2, 3)
+            .unwrap()
+            .to_notation()
+            .replace(" 2 a ", " 2 c ");
+        assert_eq!(
+            Board::from_notation(&notation),
+            Err(NotationError::InvalidField {
+                field: "next player",
+                value: "c".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_crew_counts() {
+        let notation = Board::build(2, 3)
+            .unwrap()
+            .to_notation()
+            .replacen("2.0.0", "2.0", 1);
+        assert_eq!(
+            Board::from_notation(&notation),
+            Err(NotationError::InvalidField {
+                field: "zone",
+                value: "2.0".to_owned()
+            })
+        );
+    }
+}
@@ -0,0 +1,48 @@
+use rand::{Error, RngCore};
+
+/// A small, seedable random number generator that is stored on the [`Board`](super::Board).
+///
+/// This is a `SplitMix64` generator. It is not cryptographically secure, but it is
+/// `Copy`, so the board can stay `Copy`, and the same seed always produces the same game.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(super) struct BoardRng {
+    state: u64,
+}
+
+impl BoardRng {
+    pub(super) const fn from_seed(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub(super) const fn state(self) -> u64 {
+        self.state
+    }
+}
+
+impl RngCore for BoardRng {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
@@ -0,0 +1,483 @@
+use std::cmp::Ordering;
+
+use super::{Board, BoardSpace};
+use crate::{Crew, CrewCounts, Player, Zone};
+
+/// What decided which crew controls a zone, or which crew wins the game.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DecidedBy {
+    /// The crew has more members than any other.
+    Majority,
+    /// The crews were tied and the swords tally broke the tie,
+    /// under the rules' [`TiebreakOrder`](crate::TiebreakOrder).
+    Swords,
+    /// The crews were tied and the flags tally broke the tie,
+    /// under the rules' [`TiebreakOrder`](crate::TiebreakOrder).
+    Flags,
+}
+
+/// The crew in control and what gave it control.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Control {
+    /// The crew in control.
+    pub crew: Crew,
+    /// What gave the crew control.
+    pub decided_by: DecidedBy,
+}
+
+/// Who controls a zone.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ZoneControl {
+    /// The zone.
+    pub zone: Zone,
+    /// The crew in control, or `None` if the tie could not be broken.
+    pub control: Option<Control>,
+}
+
+/// What placed a player below the player ranked just above them.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SeparatedBy {
+    /// They hold fewer members of the winning crew.
+    WinningCrew,
+    /// They hold as many of the winning crew but more of the losing crew.
+    LosingCrew,
+    /// Their hands tie, and they come later in turn order.
+    TurnOrder,
+}
+
+/// A player's place in the ranking.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Standing {
+    /// The player.
+    pub player: Player,
+    /// The crew members in the player's hand.
+    pub hand: CrewCounts,
+    /// What placed the player below the one ranked above them, or `None` for the first place.
+    pub separated_by: Option<SeparatedBy>,
+}
+
+/// How the board would score if the game ended now.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScoreReport {
+    /// Who controls each zone, in [`Zone`] order.
+    pub zones: Vec<ZoneControl>,
+    /// The number of zones each crew controls.
+    pub crew_totals: CrewCounts,
+    /// The crew that controls the most zones, or `None` if the tie could not be broken.
+    pub winning_crew: Option<Control>,
+    /// The crew that controls the fewest zones, or `None` if the tie could not be broken.
+    pub losing_crew: Option<Crew>,
+    /// The seated players, best first. Empty if there is no winning crew.
+    pub ranking: Vec<Standing>,
+}
+
+/// A player's place at the end of the game, starting from `1`.
+/// Players that cannot be separated share a place.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Placing {
+    /// The place, shared by every player the tiebreaks cannot separate.
+    pub place: u8,
+    /// The player.
+    pub player: Player,
+}
+
+impl Board {
+    /// Scores the board as if the game ended now.
+    #[must_use]
+    pub fn score_report(&self) -> ScoreReport {
+        let zones: Vec<ZoneControl> = self
+            .zones()
+            .map(|zone| ZoneControl {
+                zone,
+                control: self.get_space(zone).control(self.tiebreaks()),
+            })
+            .collect();
+
+        let mut totals = BoardSpace::default();
+        for control in zones.iter().filter_map(|zone| zone.control) {
+            totals.add_crew(control.crew, 1);
+        }
+
+        let winning_crew = totals.control(self.tiebreaks());
+        let losing_crew = totals.loser();
+        let ranking = winning_crew.map_or_else(Vec::new, |winning| {
+            self.rank_players(winning.crew, losing_crew)
+        });
+
+        ScoreReport {
+            zones,
+            crew_totals: totals.into(),
+            winning_crew,
+            losing_crew,
+            ranking,
+        }
+    }
+
+    /// Places every seated player, first to last, once the game is over.
+    ///
+    /// Players are separated by the winning crew, then the losing crew,
+    /// as in [`ScoreReport::ranking`]. Players whose hands tie on both share a place
+    /// and are listed in turn order, and the places after them are skipped, as in
+    /// `1, 1, 3`. Turn order still picks the single [`Winner`](crate::Winner) a turn
+    /// reports. If no crew wins, the game is a draw and every player shares first place.
+    ///
+    /// # Returns
+    /// The placings in order, or `None` if the game is not over.
+    #[must_use]
+    pub fn final_ranking(&self) -> Option<Vec<Placing>> {
+        if !self.is_over() {
+            return None;
+        }
+        let ranking = self.score_report().ranking;
+        if ranking.is_empty() {
+            return Some(
+                self.active_players()
+                    .map(|player| Placing { place: 1, player })
+                    .collect(),
+            );
+        }
+        let mut placings: Vec<Placing> = Vec::with_capacity(ranking.len());
+        for (place, standing) in (1..).zip(ranking) {
+            let place = match (standing.separated_by, placings.last()) {
+                (Some(SeparatedBy::TurnOrder), Some(above)) => above.place,
+                _ => place,
+            };
+            placings.push(Placing {
+                place,
+                player: standing.player,
+            });
+        }
+        Some(placings)
+    }
+
+    /// Ranks the seated players by their hands, breaking ties in turn order.
+    fn rank_players(&self, winning_crew: Crew, losing_crew: Option<Crew>) -> Vec<Standing> {
+        let num_players = self.num_players.into();
+        let mut play_order: Vec<Player> = self.active_players().collect();
+        if let Some(next) = play_order
+            .iter()
+            .position(|&player| player == self.next_player)
+        {
+            play_order.rotate_left(next);
+        }
+        play_order.sort_by(|&a, &b| {
+            BoardSpace::winning_sort(
+                *self.get_hand(b),
+                *self.get_hand(a),
+                winning_crew,
+                losing_crew,
+            )
+        });
+
+        let mut ranking: Vec<Standing> = Vec::with_capacity(num_players);
+        for player in play_order {
+            let hand = *self.get_hand(player);
+            let separated_by = ranking.last().map(|above| {
+                let winning = hand.get_crew(winning_crew);
+                let losing = losing_crew.map_or(Ordering::Equal, |crew| {
+                    hand.get_crew(crew).cmp(&above.hand.get(crew))
+                });
+                if winning != above.hand.get(winning_crew) {
+                    SeparatedBy::WinningCrew
+                } else if losing == Ordering::Equal {
+                    SeparatedBy::TurnOrder
+                } else {
+                    SeparatedBy::LosingCrew
+                }
+            });
+            ranking.push(Standing {
+                player,
+                hand: hand.into(),
+                separated_by,
+            });
+        }
+        ranking
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{RulesConfig, TiebreakOrder};
+
+    /// Rogues hold four zones, bullies five and goons two.
+    const BOARD: Board = Board {
+        swords: BoardSpace::new(0, 1, 0),
+        flags: BoardSpace::new(1, 0, 0),
+        num_players: 3,
+        ..Board::EMPTY
+    }
+    .with_zone(Zone::Red, BoardSpace::new(2, 0, 0))
+    .with_zone(Zone::Green, BoardSpace::new(0, 2, 0))
+    .with_zone(Zone::Blue, BoardSpace::new(0, 0, 2))
+    .with_zone(Zone::Orange, BoardSpace::new(1, 0, 0))
+    .with_zone(Zone::Yellow, BoardSpace::new(0, 1, 0))
+    .with_zone(Zone::Cyan, BoardSpace::new(1, 1, 0))
+    .with_zone(Zone::Magenta, BoardSpace::new(0, 1, 1))
+    .with_zone(Zone::Purple, BoardSpace::new(1, 0, 1))
+    .with_zone(Zone::White, BoardSpace::new(0, 3, 0))
+    .with_zone(Zone::Black, BoardSpace::new(0, 0, 1))
+    .with_zone(Zone::Gray, BoardSpace::new(1, 0, 0));
+
+    mod zones {
+        use super::*;
+
+        #[test]
+        fn reports_what_decided_each_zone() {
+            let report = BOARD.score_report();
+            assert_eq!(report.zones.len(), 11);
+            assert_eq!(
+                report.zones[0],
+                ZoneControl {
+                    zone: Zone::Red,
+                    control: Some(Control {
+                        crew: Crew::Rogues,
+                        decided_by: DecidedBy::Majority
+                    })
+                }
+            );
+            let cyan = report.zones.iter().find(|zone| zone.zone == Zone::Cyan);
+            assert_eq!(
+                cyan.unwrap().control,
+                Some(Control {
+                    crew: Crew::Bullies,
+                    decided_by: DecidedBy::Swords
+                })
+            );
+            let purple = report.zones.iter().find(|zone| zone.zone == Zone::Purple);
+            assert_eq!(
+                purple.unwrap().control,
+                Some(Control {
+                    crew: Crew::Rogues,
+                    decided_by: DecidedBy::Flags
+                })
+            );
+        }
+
+        #[test]
+        fn breaks_ties_in_the_configured_order() {
+            let board = Board {
+                swords: BoardSpace::new(0, 1, 0),
+                flags: BoardSpace::new(1, 0, 0),
+                rules: RulesConfig {
+                    tiebreak: TiebreakOrder::FlagsThenSwords,
+                    ..RulesConfig::STANDARD
+                },
+                ..BOARD
+            };
+            let report = board.score_report();
+            let cyan = report.zones.iter().find(|zone| zone.zone == Zone::Cyan);
+            assert_eq!(
+                cyan.unwrap().control,
+                Some(Control {
+                    crew: Crew::Rogues,
+                    decided_by: DecidedBy::Flags
+                })
+            );
+        }
+
+        #[test]
+        fn leaves_unbroken_ties_uncontrolled() {
+            let board = Board {
+                swords: BoardSpace::EMPTY,
+                flags: BoardSpace::EMPTY,
+                ..BOARD
+            };
+            let report = board.score_report();
+            let cyan = report.zones.iter().find(|zone| zone.zone == Zone::Cyan);
+            assert_eq!(cyan.unwrap().control, None);
+        }
+    }
+
+    mod crews {
+        use super::*;
+
+        #[test]
+        fn totals_the_zones_each_crew_controls() {
+            let report = BOARD.score_report();
+            assert_eq!(
+                report.crew_totals,
+                CrewCounts {
+                    rogues: 4,
+                    bullies: 5,
+                    goons: 2
+                }
+            );
+            assert_eq!(
+                report.winning_crew,
+                Some(Control {
+                    crew: Crew::Bullies,
+                    decided_by: DecidedBy::Majority
+                })
+            );
+            assert_eq!(report.losing_crew, Some(Crew::Goons));
+        }
+
+        #[test]
+        fn has_no_ranking_without_a_winning_crew() {
+            let report = Board::EMPTY.score_report();
+            assert_eq!(report.winning_crew, None);
+            assert!(report.ranking.is_empty());
+        }
+    }
+
+    mod ranking {
+        use super::*;
+
+        fn ranked(board: &Board) -> Vec<(Player, Option<SeparatedBy>)> {
+            board
+                .score_report()
+                .ranking
+                .iter()
+                .map(|standing| (standing.player, standing.separated_by))
+                .collect()
+        }
+
+        #[test]
+        fn ranks_by_winning_crew_first() {
+            let board = Board {
+                alpha: BoardSpace::new(0, 1, 0),
+                beta: BoardSpace::new(0, 3, 0),
+                gamma: BoardSpace::new(0, 2, 0),
+                ..BOARD
+            };
+            assert_eq!(
+                ranked(&board),
+                vec![
+                    (Player::Beta, None),
+                    (Player::Gamma, Some(SeparatedBy::WinningCrew)),
+                    (Player::Alpha, Some(SeparatedBy::WinningCrew)),
+                ]
+            );
+        }
+
+        #[test]
+        fn breaks_ties_with_fewer_losing_crew() {
+            let board = Board {
+                alpha: BoardSpace::new(0, 2, 1),
+                beta: BoardSpace::new(0, 2, 0),
+                gamma: BoardSpace::new(0, 1, 0),
+                ..BOARD
+            };
+            assert_eq!(
+                ranked(&board),
+                vec![
+                    (Player::Beta, None),
+                    (Player::Alpha, Some(SeparatedBy::LosingCrew)),
+                    (Player::Gamma, Some(SeparatedBy::WinningCrew)),
+                ]
+            );
+        }
+
+        #[test]
+        fn breaks_remaining_ties_in_turn_order() {
+            let board = Board {
+                alpha: BoardSpace::new(1, 2, 0),
+                beta: BoardSpace::new(0, 2, 0),
+                gamma: BoardSpace::new(0, 2, 0),
+                next_player: Player::Beta,
+                ..BOARD
+            };
+            assert_eq!(
+                ranked(&board),
+                vec![
+                    (Player::Beta, None),
+                    (Player::Gamma, Some(SeparatedBy::TurnOrder)),
+                    (Player::Alpha, Some(SeparatedBy::TurnOrder)),
+                ]
+            );
+        }
+
+        #[test]
+        fn places_every_player_at_game_end() {
+            let board = Board {
+                alpha: BoardSpace::new(0, 1, 0),
+                beta: BoardSpace::new(0, 3, 0),
+                gamma: BoardSpace::new(0, 2, 0),
+                consecutive_negotiations: 3,
+                ..BOARD
+            };
+            assert_eq!(
+                board.final_ranking(),
+                Some(vec![
+                    Placing {
+                        place: 1,
+                        player: Player::Beta
+                    },
+                    Placing {
+                        place: 2,
+                        player: Player::Gamma
+                    },
+                    Placing {
+                        place: 3,
+                        player: Player::Alpha
+                    },
+                ])
+            );
+        }
+
+        #[test]
+        fn shares_places_that_only_turn_order_separates() {
+            let board = Board {
+                alpha: BoardSpace::new(1, 1, 0),
+                beta: BoardSpace::new(0, 2, 0),
+                gamma: BoardSpace::new(0, 2, 0),
+                next_player: Player::Gamma,
+                consecutive_negotiations: 3,
+                ..BOARD
+            };
+            assert_eq!(
+                board.final_ranking(),
+                Some(vec![
+                    Placing {
+                        place: 1,
+                        player: Player::Gamma
+                    },
+                    Placing {
+                        place: 1,
+                        player: Player::Beta
+                    },
+                    Placing {
+                        place: 3,
+                        player: Player::Alpha
+                    },
+                ])
+            );
+        }
+
+        #[test]
+        fn shares_first_place_in_a_draw() {
+            let board = Board {
+                num_players: 2,
+                consecutive_negotiations: 2,
+                ..Board::EMPTY
+            };
+            let places: Vec<u8> = board
+                .final_ranking()
+                .unwrap()
+                .iter()
+                .map(|placing| placing.place)
+                .collect();
+            assert_eq!(places, [1, 1]);
+        }
+
+        #[test]
+        fn has_no_final_ranking_before_game_end() {
+            assert_eq!(BOARD.final_ranking(), None);
+        }
+
+        #[test]
+        fn ranks_only_seated_players() {
+            let report = BOARD.score_report();
+            assert_eq!(report.ranking.len(), 3);
+            assert_eq!(BOARD.score(), Some(Player::Alpha));
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! The serialized shape of a [`Board`].
+//!
+//! A board is written as `{ "version": 2, "board": { .. } }` so that saved games
+//! can be recognised, and rejected or migrated, if the layout ever changes.
+//!
+//! Boards saved as version 1, before maps, are read onto the standard map.
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{Bag, Board, BoardRng, BoardSpace, Layout, MAX_ZONES};
+use crate::{CrewCounts, Player, RulesConfig, Zone};
+
+/// The current version of the serialized board layout.
+///
+/// Version 2 replaced the eleven named zones with the zones and layout of a map.
+const VERSION: u32 = 2;
+
+#[derive(Serialize)]
+struct VersionedRef<'a> {
+    version: u32,
+    #[serde(with = "Board")]
+    board: &'a Board,
+}
+
+#[derive(Deserialize)]
+struct Versioned {
+    version: u32,
+    board: SavedBoard,
+}
+
+/// The fields of every version of the board that can still be read.
+/// Those only some versions have are optional.
+#[derive(Deserialize)]
+struct SavedBoard {
+    bag: Bag,
+    rng: BoardRng,
+
+    /// The zones and layout, from version 2.
+    zones: Option<[BoardSpace; MAX_ZONES]>,
+    layout: Option<Layout>,
+
+    /// The zones of the standard map, in version 1.
+    red: Option<BoardSpace>,
+    blue: Option<BoardSpace>,
+    green: Option<BoardSpace>,
+    orange: Option<BoardSpace>,
+    yellow: Option<BoardSpace>,
+    cyan: Option<BoardSpace>,
+    magenta: Option<BoardSpace>,
+    purple: Option<BoardSpace>,
+    white: Option<BoardSpace>,
+    black: Option<BoardSpace>,
+    gray: Option<BoardSpace>,
+
+    alpha: BoardSpace,
+    beta: BoardSpace,
+    gamma: BoardSpace,
+    delta: BoardSpace,
+    epsilon: BoardSpace,
+
+    swords: BoardSpace,
+    flags: BoardSpace,
+
+    num_players: u8,
+    next_player: Player,
+    current_negotiation: bool,
+    consecutive_negotiations: u8,
+
+    /// Boards saved before rules could be chosen were played by the standard rules.
+    #[serde(default)]
+    rules: RulesConfig,
+}
+
+impl SavedBoard {
+    /// Builds the board, reading the zones as the given version wrote them,
+    /// and checks every invariant but the piece count.
+    fn into_board(self, version: u32) -> Result<Board, String> {
+        let (zones, layout) = match version {
+            1 => (self.standard_zones()?, Layout::STANDARD),
+            VERSION => {
+                let zones = self.zones.ok_or("missing field `zones`")?;
+                let layout = self.layout.ok_or("missing field `layout`")?;
+                if let Some(index) = (layout.num_zones()..MAX_ZONES)
+                    .find(|&index| CrewCounts::from(zones[index]).total() > 0)
+                {
+                    return Err(format!("zone {index} is not on the map but holds crew"));
+                }
+                (zones, layout)
+            }
+            _ => {
+                return Err(format!(
+                    "unsupported board version {version}, expected 1 to {VERSION}"
+                ))
+            }
+        };
+        let board = Board {
+            bag: self.bag,
+            rng: self.rng,
+            zones,
+            layout,
+            alpha: self.alpha,
+            beta: self.beta,
+            gamma: self.gamma,
+            delta: self.delta,
+            epsilon: self.epsilon,
+            swords: self.swords,
+            flags: self.flags,
+            num_players: self.num_players,
+            next_player: self.next_player,
+            current_negotiation: self.current_negotiation,
+            consecutive_negotiations: self.consecutive_negotiations,
+            rules: self.rules,
+        };
+        board.check_structure().map_err(|error| error.to_string())?;
+        Ok(board)
+    }
+
+    /// The named zones of version 1, placed on the standard map.
+    fn standard_zones(&self) -> Result<[BoardSpace; MAX_ZONES], String> {
+        let named = [
+            self.red,
+            self.blue,
+            self.green,
+            self.orange,
+            self.yellow,
+            self.cyan,
+            self.magenta,
+            self.purple,
+            self.white,
+            self.black,
+            self.gray,
+        ];
+        let mut zones = [BoardSpace::EMPTY; MAX_ZONES];
+        for (zone, space) in Zone::STANDARD.into_iter().zip(named) {
+            zones[zone.index()] = space.ok_or_else(|| format!("missing field `{zone}`"))?;
+        }
+        Ok(zones)
+    }
+}
+
+impl Serialize for Board {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        VersionedRef {
+            version: VERSION,
+            board: self,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Board {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let Versioned { version, board } = Versioned::deserialize(deserializer)?;
+        board.into_board(version).map_err(D::Error::custom)
+    }
+}
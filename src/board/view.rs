@@ -0,0 +1,390 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use super::{Bag, Board, BoardRng, BoardSpace};
+use crate::{Action, Crew, CrewCounts, Player, Zone};
+
+/// What anyone at the table can see: the zones, the tallies,
+/// how many crew members each player holds and how many are in the bag.
+#[derive(Clone, Copy)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[cfg_attr(test, derive(PartialEq, Eq))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpectatorView {
+    /// The board with every hand, the bag and the random number generator cleared.
+    board: Board,
+    hand_sizes: [u16; 5],
+    bag_total: u16,
+}
+
+/// What one player can see: everything in the [`SpectatorView`] and their own hand.
+#[derive(Clone, Copy)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[cfg_attr(test, derive(PartialEq, Eq))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerView {
+    public: SpectatorView,
+    viewer: Player,
+    hand: CrewCounts,
+}
+
+/// How strongly to lean each hidden hand towards each crew when dealing out
+/// what a player cannot see, see [`PlayerView::sample_weighted_determinization`].
+///
+/// A weight of `2.0` makes a hand twice as likely as the bag to be dealt a member
+/// of that crew, for the same room left. Every weight starts at `1.0`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HandWeights {
+    weights: [[f64; 3]; 5],
+}
+
+impl HandWeights {
+    /// Weights that deal every hidden crew member as likely to any place as another.
+    pub const UNIFORM: Self = Self {
+        weights: [[1.0; 3]; 5],
+    };
+
+    /// Returns the weight of a crew in a player's hand.
+    #[must_use]
+    pub const fn get(&self, player: Player, crew: Crew) -> f64 {
+        self.weights[player as usize][crew as usize]
+    }
+
+    /// Sets the weight of a crew in a player's hand. Negative weights count as zero.
+    pub const fn set(&mut self, player: Player, crew: Crew, weight: f64) {
+        self.weights[player as usize][crew as usize] = weight.max(0.0);
+    }
+}
+
+impl Default for HandWeights {
+    fn default() -> Self {
+        Self::UNIFORM
+    }
+}
+
+impl Board {
+    /// Returns what the given player can see of the board.
+    #[must_use]
+    pub fn view_for(&self, player: Player) -> PlayerView {
+        PlayerView {
+            public: self.spectator_view(),
+            viewer: player,
+            hand: self.hand(player),
+        }
+    }
+
+    /// Returns what someone without a hand can see of the board.
+    #[must_use]
+    pub fn spectator_view(&self) -> SpectatorView {
+        let mut hand_sizes = [0; 5];
+        for (size, player) in hand_sizes.iter_mut().zip(enum_iterator::all::<Player>()) {
+            *size = self.hand(player).total();
+        }
+
+        SpectatorView {
+            board: Self {
+                alpha: BoardSpace::EMPTY,
+                beta: BoardSpace::EMPTY,
+                gamma: BoardSpace::EMPTY,
+                delta: BoardSpace::EMPTY,
+                epsilon: BoardSpace::EMPTY,
+                bag: Bag::EMPTY,
+                rng: BoardRng::from_seed(0),
+                ..*self
+            },
+            hand_sizes,
+            bag_total: self.bag().total(),
+        }
+    }
+}
+
+impl SpectatorView {
+    /// Returns the crew members in a zone.
+    #[must_use]
+    pub fn zone(&self, zone: Zone) -> CrewCounts {
+        self.board.zone(zone)
+    }
+
+    /// Returns every zone on the map, in order.
+    pub fn zones(&self) -> impl Iterator<Item = Zone> + Clone {
+        self.board.zones()
+    }
+
+    /// Returns the crew members played to battle.
+    #[must_use]
+    pub fn swords(&self) -> CrewCounts {
+        self.board.swords()
+    }
+
+    /// Returns the crew members played to march.
+    #[must_use]
+    pub fn flags(&self) -> CrewCounts {
+        self.board.flags()
+    }
+
+    /// Returns the number of crew members in a player's hand.
+    /// Players that are not seated hold none.
+    #[must_use]
+    pub const fn hand_size(&self, player: Player) -> u16 {
+        self.hand_sizes[player as usize]
+    }
+
+    /// Returns the number of crew members in the bag.
+    #[must_use]
+    pub const fn bag_total(&self) -> u16 {
+        self.bag_total
+    }
+
+    /// Returns the number of players in the game.
+    #[must_use]
+    pub const fn num_players(&self) -> u8 {
+        self.board.num_players()
+    }
+
+    /// Returns the player whose turn it is.
+    #[must_use]
+    pub const fn next_player(&self) -> Player {
+        self.board.next_player()
+    }
+
+    /// Returns whether a negotiation is waiting for its `EndNegotiation`.
+    #[must_use]
+    pub const fn is_negotiating(&self) -> bool {
+        self.board.is_negotiating()
+    }
+
+    /// Returns the number of negotiations in a row.
+    #[must_use]
+    pub const fn consecutive_negotiations(&self) -> u8 {
+        self.board.consecutive_negotiations()
+    }
+}
+
+impl PlayerView {
+    /// Returns the player this view belongs to.
+    #[must_use]
+    pub const fn viewer(&self) -> Player {
+        self.viewer
+    }
+
+    /// Returns the crew members in the viewer's hand.
+    #[must_use]
+    pub const fn hand(&self) -> CrewCounts {
+        self.hand
+    }
+
+    /// Returns everything the viewer can see apart from their own hand.
+    #[must_use]
+    pub const fn public(&self) -> &SpectatorView {
+        &self.public
+    }
+
+    /// Lists every action the viewer may take, as [`Board::legal_actions`] does,
+    /// or none if it is not their turn.
+    #[must_use]
+    pub fn legal_actions(&self) -> Vec<Action> {
+        if self.viewer != self.public.next_player() {
+            return Vec::new();
+        }
+        self.public
+            .board
+            .legal_actions_with(self.hand, self.public.bag_total == 0)
+    }
+
+    /// Returns the crew members the viewer cannot see, in the other hands and the bag.
+    ///
+    /// Games are only set up, and boards only read, with at most 255 members of each crew,
+    /// so the counts fit.
+    #[must_use]
+    pub fn unseen(&self) -> CrewCounts {
+        let board = self.known_board();
+        let hidden = |crew| {
+            let hidden = board
+                .pieces_in_play(crew)
+                .saturating_sub(board.count_pieces(crew));
+            u8::try_from(hidden).unwrap_or(u8::MAX)
+        };
+        CrewCounts {
+            rogues: hidden(Crew::Rogues),
+            bullies: hidden(Crew::Bullies),
+            goons: hidden(Crew::Goons),
+        }
+    }
+
+    /// The board as far as the viewer knows it: their own hand is filled in,
+    /// while the other hands and the bag are empty.
+    pub(crate) const fn known_board(&self) -> Board {
+        let mut board = self.public.board;
+        *board.get_hand_mut(self.viewer) =
+            BoardSpace::new(self.hand.rogues, self.hand.bullies, self.hand.goons);
+        board
+    }
+
+    /// Returns a whole board that the viewer cannot tell apart from the real one.
+    ///
+    /// The crew members the viewer cannot see are dealt at random to the other hands
+    /// and the bag, each getting as many as it holds, so that every way of dealing them
+    /// is as likely as any other. The random number generator of the board is seeded
+    /// from `rng`.
+    pub fn sample_determinization<R: Rng + ?Sized>(&self, rng: &mut R) -> Board {
+        self.sample_weighted_determinization(rng, &HandWeights::UNIFORM)
+    }
+
+    /// Returns a whole board that the viewer cannot tell apart from the real one,
+    /// dealing each hidden crew member to a hand in proportion to the room left in it
+    /// and its weight for that crew, and to the bag in proportion to the room left in it.
+    ///
+    /// When every place with room weighs nothing for a crew, the weights are ignored.
+    pub fn sample_weighted_determinization<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        weights: &HandWeights,
+    ) -> Board {
+        let mut board = self.known_board();
+        let unseen = self.unseen();
+        let mut unseen: Vec<Crew> = enum_iterator::all::<Crew>()
+            .flat_map(|crew| std::iter::repeat_n(crew, unseen.get(crew).into()))
+            .collect();
+        unseen.shuffle(rng);
+
+        // Each other hand and then the bag, with the room left in it.
+        let mut places: Vec<(Option<Player>, u16)> = enum_iterator::all::<Player>()
+            .filter(|&player| player != self.viewer)
+            .map(|player| (Some(player), self.public.hand_size(player)))
+            .chain([(None, self.public.bag_total)])
+            .collect();
+        let mut bag = Vec::new();
+        for crew in unseen {
+            let rooms: Vec<f64> = places.iter().map(|&(_, room)| f64::from(room)).collect();
+            let weighted: Vec<f64> = places
+                .iter()
+                .zip(&rooms)
+                .map(|(&(place, _), room)| room * place.map_or(1.0, |p| weights.get(p, crew)))
+                .collect();
+            let Some(index) = pick_weighted(rng, &weighted).or_else(|| pick_weighted(rng, &rooms))
+            else {
+                break;
+            };
+            match places[index] {
+                (Some(player), _) => board.get_hand_mut(player).add_crew(crew, 1),
+                (None, _) => bag.push(crew),
+            }
+            places[index].1 -= 1;
+        }
+        board.bag = Bag::from_slice(&bag);
+        board.rng = BoardRng::from_seed(rng.next_u64());
+        board
+    }
+}
+
+/// Picks an index with a chance in proportion to its weight,
+/// or `None` if the weights do not add up to a positive, finite total.
+fn pick_weighted<R: Rng + ?Sized>(rng: &mut R, weights: &[f64]) -> Option<usize> {
+    let total: f64 = weights.iter().sum();
+    if !(total > 0.0 && total.is_finite()) {
+        return None;
+    }
+    let mut roll = rng.gen_range(0.0..total);
+    weights
+        .iter()
+        .position(|&weight| {
+            roll -= weight;
+            roll < 0.0
+        })
+        .or_else(|| weights.iter().rposition(|&weight| weight > 0.0))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Crew, TurnResult};
+
+    #[test]
+    fn player_view_shows_only_own_hand() {
+        let board = Board::build(3, 8).unwrap();
+        let view = board.view_for(Player::Beta);
+        assert_eq!(view.viewer(), Player::Beta);
+        assert_eq!(view.hand(), CrewCounts::from(board.beta));
+        assert_eq!(view.public(), &board.spectator_view());
+    }
+
+    #[test]
+    fn player_view_lists_the_same_actions_as_the_board() {
+        let board = Board::build(3, 8).unwrap();
+        let view = board.view_for(Player::Alpha);
+        assert_eq!(view.legal_actions(), board.legal_actions());
+        assert!(board.view_for(Player::Beta).legal_actions().is_empty());
+
+        let TurnResult(board, _) = board.process_action(Action::StartNegotiation);
+        let view = board.view_for(Player::Alpha);
+        assert_eq!(view.legal_actions(), board.legal_actions());
+    }
+
+    #[test]
+    fn determinizations_match_the_view() {
+        let board = Board::build(4, 6).unwrap();
+        let view = board.view_for(Player::Beta);
+        let mut rng = BoardRng::from_seed(6);
+        for _ in 0..10 {
+            let sample = view.sample_determinization(&mut rng);
+            assert_eq!(sample.check_invariants(), Ok(()));
+            assert_eq!(sample.hand(Player::Beta), board.hand(Player::Beta));
+            assert_eq!(sample.bag().total(), board.bag().total());
+            assert_eq!(sample.spectator_view(), board.spectator_view());
+        }
+    }
+
+    #[test]
+    fn weighted_determinizations_lean_towards_the_weights() {
+        let board = Board::build(4, 6).unwrap();
+        let view = board.view_for(Player::Beta);
+        let mut weights = HandWeights::UNIFORM;
+        weights.set(Player::Alpha, Crew::Rogues, 0.0);
+        weights.set(Player::Alpha, Crew::Bullies, 0.0);
+        weights.set(Player::Alpha, Crew::Goons, 10.0);
+
+        let mut rng = BoardRng::from_seed(6);
+        let mut goons = [0_u32; 2];
+        for _ in 0..50 {
+            let uniform = view.sample_determinization(&mut rng);
+            let weighted = view.sample_weighted_determinization(&mut rng, &weights);
+            assert_eq!(weighted.check_invariants(), Ok(()));
+            assert_eq!(weighted.spectator_view(), board.spectator_view());
+            goons[0] += u32::from(uniform.hand(Player::Alpha).goons);
+            goons[1] += u32::from(weighted.hand(Player::Alpha).goons);
+        }
+        assert!(goons[1] > goons[0] * 2, "{goons:?}");
+    }
+
+    #[test]
+    fn spectator_view_hides_hands_and_bag() {
+        let board = Board::build(3, 8).unwrap();
+        let view = board.spectator_view();
+        assert_eq!(view.board.alpha, BoardSpace::EMPTY);
+        assert_eq!(view.board.bag, Bag::EMPTY);
+        assert_eq!(view.hand_size(Player::Alpha), 8);
+        assert_eq!(view.hand_size(Player::Delta), 0);
+        assert_eq!(view.bag_total(), 57 - 16 - 24);
+    }
+
+    #[test]
+    fn spectator_view_does_not_depend_on_hidden_information() {
+        let board = Board::build(2, 8).unwrap();
+        let shuffled = Board {
+            alpha: board.beta,
+            beta: board.alpha,
+            rng: BoardRng::from_seed(99),
+            ..board
+        };
+        assert_eq!(board.spectator_view(), shuffled.spectator_view());
+    }
+
+    #[test]
+    fn spectator_view_shows_public_zones() {
+        let board = Board::build(2, 8).unwrap();
+        let view = board.spectator_view();
+        assert_eq!(view.zone(Zone::Red).get(Crew::Rogues), 2);
+        assert_eq!(view.zone(Zone::Cyan), board.zone(Zone::Cyan));
+    }
+}
@@ -0,0 +1,465 @@
+use std::fmt;
+
+use crate::notation::{Named, ZoneNames};
+use crate::{Action, Crew, Outcome, Player, Zone};
+
+/// An error that can occur when performing an action.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Error {
+    /// The action that caused the error.
+    pub action: Action,
+    /// The reason the action failed.
+    pub kind: ErrorKind,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.kind.fmt(f)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The reasons an action can fail.
+///
+/// Zones are named as on the standard map; [`Map::display_error`](crate::Map::display_error)
+/// names them as on another.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ErrorKind {
+    /// The zones of a `March` are not adjacent.
+    NotAdjacent {
+        /// The zone the crew would march from.
+        from: Zone,
+        /// The zone the crew would march to.
+        to: Zone,
+    },
+    /// A `Battle` tried to remove members of the attacking crew.
+    RemovingAttackingCrew(Crew),
+    /// A `Battle` did not remove any crew members.
+    NothingRemoved,
+    /// A zone does not hold enough crew members for the action.
+    NotEnoughCrew {
+        /// The zone that is short.
+        zone: Zone,
+        /// The crew that is short.
+        crew: Crew,
+        /// The number of crew members the action needs.
+        needed: u8,
+        /// The number of crew members in the zone.
+        available: u8,
+    },
+    /// The player does not hold the crew member they tried to play.
+    MissingFromHand {
+        /// The player that tried to play the crew member.
+        player: Player,
+        /// The crew that is missing.
+        crew: Crew,
+    },
+    /// Only `EndNegotiation` may be taken while a negotiation is in progress.
+    NegotiationInProgress,
+    /// `EndNegotiation` was taken without a preceding `StartNegotiation`.
+    NoNegotiationInProgress,
+    /// The action names a zone that is not on the map.
+    UnknownZone(Zone),
+    /// `StartNegotiation` was taken with nothing left in the bag,
+    /// and the rules do not allow negotiating without drawing
+    /// or the player holds nothing to end the negotiation with.
+    EmptyBag,
+    /// The game is over, so no more actions may be taken.
+    GameOver,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Named {
+            value: *self,
+            names: ZoneNames::Standard,
+        }
+        .fmt(f)
+    }
+}
+
+impl fmt::Display for Named<'_, ErrorKind> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let zone = |zone| self.names.zone(zone);
+        match self.value {
+            ErrorKind::NotAdjacent { from, to } => write!(
+                f,
+                "Cannot march between non-adjacent zones {} and {}",
+                zone(from),
+                zone(to)
+            ),
+            ErrorKind::RemovingAttackingCrew(crew) => {
+                write!(f, "Cannot remove {crew} from the attacking crew")
+            }
+            ErrorKind::NothingRemoved => {
+                write!(f, "Must remove at least one crew member when attacking")
+            }
+            ErrorKind::NotEnoughCrew {
+                zone: short,
+                crew,
+                needed,
+                available,
+            } => write!(
+                f,
+                "Not enough {crew} in {}: {needed} needed, {available} available",
+                zone(short)
+            ),
+            ErrorKind::MissingFromHand { player, crew } => {
+                write!(f, "{player:?} has no {crew} in hand")
+            }
+            ErrorKind::NegotiationInProgress => write!(f, "Negotiation in progress"),
+            ErrorKind::NoNegotiationInProgress => write!(f, "No negotiation in progress"),
+            ErrorKind::UnknownZone(unknown) => {
+                write!(f, "There is no zone {} on the map", zone(unknown))
+            }
+            ErrorKind::EmptyBag => write!(f, "The bag is empty"),
+            ErrorKind::GameOver => write!(f, "The game is over"),
+        }
+    }
+}
+
+/// An error that can occur when starting a game.
+#[non_exhaustive]
+#[allow(variant_size_differences)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SetupError {
+    /// The game supports between 2 and 5 players.
+    BadPlayerCount(u8),
+    /// The rules deal out more crew members than the bag holds.
+    NotEnoughCrew {
+        /// The number of crew members the hands and zones need.
+        needed: u16,
+        /// The number of crew members in the bag.
+        available: u16,
+    },
+    /// The rules end the game after zero negotiations in a row, before it begins.
+    NoNegotiationsToEnd,
+    /// The bag and the home bases hold more than 255 members of a crew.
+    TooManyCrew {
+        /// The crew with too many members.
+        crew: Crew,
+        /// The number of members the bag and home bases hold.
+        count: u16,
+    },
+}
+
+impl fmt::Display for SetupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadPlayerCount(count) => {
+                write!(f, "The game supports between 2 and 5 players, not {count}")
+            }
+            Self::NotEnoughCrew { needed, available } => write!(
+                f,
+                "The rules deal out {needed} crew members but the bag holds {available}"
+            ),
+            Self::NoNegotiationsToEnd => {
+                write!(f, "The game must end after at least one negotiation")
+            }
+            Self::TooManyCrew { crew, count } => {
+                write!(f, "The game is played with {count} {crew}, more than 255")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SetupError {}
+
+/// A way in which a board is inconsistent, found by
+/// [`Board::check_invariants`](crate::Board::check_invariants).
+#[non_exhaustive]
+#[allow(variant_size_differences)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InvariantError {
+    /// The zones, hands, bag and tallies do not hold every member of a crew, or hold too many.
+    /// A count that overflowed shows up here, as counts stop at `u8::MAX`.
+    PieceCount {
+        /// The crew that is miscounted.
+        crew: Crew,
+        /// The number of members the game is played with.
+        expected: u16,
+        /// The number of members on the board.
+        found: u16,
+    },
+    /// The number of players is not between 2 and 5.
+    PlayerCount(u8),
+    /// The next player is not seated.
+    UnseatedNextPlayer(Player),
+    /// A player that is not seated holds crew members,
+    /// although the rules leave unused seats empty.
+    UnseatedHand(Player),
+    /// There have been more negotiations in a row than it takes to end the game.
+    ConsecutiveNegotiations(u8),
+    /// A negotiation is in progress although the game is over.
+    NegotiationAfterGameEnd,
+    /// The rules and the map play with more than 255 members of a crew.
+    TooManyCrew {
+        /// The crew with too many members.
+        crew: Crew,
+        /// The number of members the game is played with.
+        count: u16,
+    },
+}
+
+impl fmt::Display for InvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PieceCount {
+                crew,
+                expected,
+                found,
+            } => write!(f, "Expected {expected} {crew}, found {found}"),
+            Self::PlayerCount(count) => write!(f, "Invalid number of players: {count}"),
+            Self::UnseatedNextPlayer(player) => {
+                write!(f, "The next player, {player:?}, is not seated")
+            }
+            Self::UnseatedHand(player) => {
+                write!(f, "{player:?} is not seated but holds crew members")
+            }
+            Self::ConsecutiveNegotiations(count) => {
+                write!(
+                    f,
+                    "{count} negotiations in a row is more than it takes to end the game"
+                )
+            }
+            Self::NegotiationAfterGameEnd => {
+                write!(f, "Negotiation in progress after the game ended")
+            }
+            Self::TooManyCrew { crew, count } => {
+                write!(f, "The game is played with {count} {crew}, more than 255")
+            }
+        }
+    }
+}
+
+impl std::error::Error for InvariantError {}
+
+/// An error that can occur when reading a position or an action from its notation.
+/// See [`Board::from_notation`](crate::Board::from_notation) and [`Action`].
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NotationError {
+    /// The notation does not have the expected number of space separated fields.
+    FieldCount {
+        /// The number of fields the notation needs.
+        expected: usize,
+        /// The number of fields given.
+        found: usize,
+    },
+    /// The number of zones does not match the map.
+    ZoneCount {
+        /// The number of zones on the map.
+        expected: usize,
+        /// The number of zones given.
+        found: usize,
+    },
+    /// The number of hands does not match the number of players.
+    HandCount {
+        /// The number of players.
+        expected: u8,
+        /// The number of hands given.
+        found: usize,
+    },
+    /// A field could not be read.
+    InvalidField {
+        /// The name of the field.
+        field: &'static str,
+        /// The text that could not be read.
+        value: String,
+    },
+    /// The map of a game record could not be read.
+    InvalidMap(MapError),
+    /// The position could not come up in a game,
+    /// for any reason but the number of crew members.
+    InvalidPosition(InvariantError),
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FieldCount { expected, found } => {
+                write!(f, "Expected {expected} fields, found {found}")
+            }
+            Self::ZoneCount { expected, found } => {
+                write!(f, "Expected {expected} zones, found {found}")
+            }
+            Self::HandCount { expected, found } => {
+                write!(f, "Expected {expected} hands, found {found}")
+            }
+            Self::InvalidField { field, value } => write!(f, "Invalid {field}: {value:?}"),
+            Self::InvalidMap(error) => write!(f, "Invalid map: {error}"),
+            Self::InvalidPosition(error) => write!(f, "Invalid position: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for NotationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidMap(error) => Some(error),
+            Self::InvalidPosition(error) => Some(error),
+            Self::FieldCount { .. }
+            | Self::ZoneCount { .. }
+            | Self::HandCount { .. }
+            | Self::InvalidField { .. } => None,
+        }
+    }
+}
+
+/// An error that can occur when replaying a [`GameRecord`](crate::GameRecord).
+#[non_exhaustive]
+#[allow(variant_size_differences)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The recorded game could not be started.
+    Setup(SetupError),
+    /// A turn had a different outcome than the one recorded.
+    Mismatch {
+        /// The index of the turn in the record.
+        turn: usize,
+        /// The recorded outcome.
+        expected: Outcome,
+        /// The outcome when the turn was replayed.
+        found: Outcome,
+    },
+    /// The replay ended in a different position than the one recorded.
+    EndMismatch,
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Setup(error) => write!(f, "Cannot start the recorded game: {error}"),
+            Self::Mismatch {
+                turn,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Turn {turn} was recorded as {expected} but replayed as {found}"
+            ),
+            Self::EndMismatch => write!(f, "The replay ended in a different position"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Setup(error) => Some(error),
+            Self::Mismatch { .. } | Self::EndMismatch => None,
+        }
+    }
+}
+
+/// An error that can occur when building a [`Map`](crate::Map).
+///
+/// Zones are written by index, since the map they belong to may not have been built.
+#[non_exhaustive]
+#[allow(variant_size_differences)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MapError {
+    /// A map has between 1 and 32 zones.
+    ZoneCount(usize),
+    /// A zone name is not a single word, holds `:`, `/`, `.` or `->`,
+    /// starts with `#` or is a number.
+    InvalidName(String),
+    /// Two zones have the same name.
+    DuplicateName(String),
+    /// An adjacency names a zone that is not on the map.
+    UnknownZone(Zone),
+    /// A zone is listed as adjacent to itself.
+    SelfAdjacent(Zone),
+    /// A zone is adjacent to another that is not adjacent back.
+    Asymmetric {
+        /// The zone with the adjacency.
+        from: Zone,
+        /// The zone without the adjacency back.
+        to: Zone,
+    },
+    /// A line of a map in text form could not be read.
+    InvalidLine(String),
+    /// A map in text form names a zone that it does not list.
+    UnknownName(String),
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZoneCount(count) => write!(f, "A map has between 1 and 32 zones, not {count}"),
+            Self::InvalidName(name) => write!(f, "Invalid zone name {name:?}"),
+            Self::DuplicateName(name) => write!(f, "More than one zone is named {name:?}"),
+            Self::UnknownZone(zone) => {
+                write!(f, "There is no zone {} on the map", zone.index())
+            }
+            Self::SelfAdjacent(zone) => write!(f, "Zone {} is adjacent to itself", zone.index()),
+            Self::Asymmetric { from, to } => {
+                write!(
+                    f,
+                    "Zone {} is adjacent to {}, but not the other way",
+                    from.index(),
+                    to.index()
+                )
+            }
+            Self::InvalidLine(line) => write!(f, "Invalid map line {line:?}"),
+            Self::UnknownName(name) => write!(f, "No zone is named {name:?}"),
+        }
+    }
+}
+
+impl std::error::Error for MapError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn messages_name_crews_as_written() {
+        let missing = ErrorKind::MissingFromHand {
+            player: Player::Beta,
+            crew: Crew::Goons,
+        };
+        assert_eq!(missing.to_string(), "Beta has no goons in hand");
+        let not_enough = ErrorKind::NotEnoughCrew {
+            zone: Zone::Red,
+            crew: Crew::Rogues,
+            needed: 3,
+            available: 1,
+        };
+        assert_eq!(
+            not_enough.to_string(),
+            "Not enough rogues in red: 3 needed, 1 available"
+        );
+        assert_eq!(
+            ErrorKind::RemovingAttackingCrew(Crew::Bullies).to_string(),
+            "Cannot remove bullies from the attacking crew"
+        );
+    }
+
+    #[test]
+    fn messages_name_zones_as_the_map_does() {
+        let map: crate::Map = "keep : yard\nyard : keep".parse().unwrap();
+        let not_adjacent = ErrorKind::NotAdjacent {
+            from: Zone::new(1),
+            to: Zone::new(5),
+        };
+        assert_eq!(
+            map.display_error(not_adjacent).to_string(),
+            "Cannot march between non-adjacent zones yard and 5"
+        );
+        assert_eq!(
+            not_adjacent.to_string(),
+            "Cannot march between non-adjacent zones blue and cyan"
+        );
+        assert_eq!(
+            MapError::SelfAdjacent(Zone::new(1)).to_string(),
+            "Zone 1 is adjacent to itself"
+        );
+    }
+}
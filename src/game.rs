@@ -0,0 +1,250 @@
+use crate::{
+    take_turn, Action, Board, Error, GameRecord, GameStart, Map, Outcome, RecordedTurn,
+    ReplayError, TurnResult, Winner,
+};
+
+/// A game with its history, so that turns can be taken back and replayed.
+///
+/// Only actions that succeed are kept. Taking a new action after an undo
+/// discards the turns that could have been redone.
+#[derive(Clone)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct Game {
+    /// The board before the first turn, followed by the board after each turn.
+    boards: Vec<Board>,
+    turns: Vec<RecordedTurn>,
+    /// The number of turns currently applied.
+    position: usize,
+    /// The map the game is played on, kept for its record.
+    map: Map,
+}
+
+impl Game {
+    /// Starts tracking a game from the given board.
+    ///
+    /// Zones of a board on a map other than the standard one are recorded by index;
+    /// use [`Game::on_map`] to keep their names.
+    #[must_use]
+    pub fn new(board: Board) -> Self {
+        let map = board.default_map();
+        Self {
+            boards: vec![board],
+            turns: Vec::new(),
+            position: 0,
+            map,
+        }
+    }
+
+    /// Starts tracking a game from the given board, played on the given map.
+    ///
+    /// Returns `None` if the board is not on that map.
+    #[must_use]
+    pub fn on_map(board: Board, map: Map) -> Option<Self> {
+        board.is_on_map(&map).then(|| Self {
+            map,
+            ..Self::new(board)
+        })
+    }
+
+    /// Rebuilds a game from a record, with every recorded turn applied.
+    /// Rejected turns in the record are skipped.
+    ///
+    /// # Errors
+    /// Returns a [`ReplayError`] if the record does not replay cleanly.
+    pub fn from_record(record: &GameRecord) -> Result<Self, ReplayError> {
+        let boards = record.replay()?;
+        let mut game = Self {
+            map: record.map().clone(),
+            ..Self::new(boards[0])
+        };
+        for (turn, &board) in record.turns.iter().zip(&boards[1..]) {
+            if !matches!(turn.outcome, Outcome::Rejected(_)) {
+                game.boards.push(board);
+                game.turns.push(*turn);
+            }
+        }
+        game.position = game.turns.len();
+        Ok(game)
+    }
+
+    /// The board as of the current turn.
+    #[must_use]
+    pub fn board(&self) -> Board {
+        self.boards[self.position]
+    }
+
+    /// Takes a turn on the current board.
+    ///
+    /// # Errors
+    /// Returns the [`Error`] from [`take_turn`] and leaves the game unchanged.
+    pub fn play(&mut self, action: Action) -> Result<Option<Winner>, Error> {
+        let TurnResult(board, result) = take_turn(self.board(), action);
+        let winner = result?;
+
+        self.boards.truncate(self.position + 1);
+        self.turns.truncate(self.position);
+        self.boards.push(board);
+        self.turns.push(RecordedTurn {
+            action,
+            outcome: winner.map_or(Outcome::Continued, Outcome::Ended),
+        });
+        self.position += 1;
+        Ok(winner)
+    }
+
+    /// Takes back the last turn.
+    ///
+    /// # Returns
+    /// The action that was taken back, or `None` if there is nothing to undo.
+    pub fn undo(&mut self) -> Option<Action> {
+        self.position = self.position.checked_sub(1)?;
+        Some(self.turns[self.position].action)
+    }
+
+    /// Takes again the last turn that was taken back.
+    ///
+    /// # Returns
+    /// The action that was taken again, or `None` if there is nothing to redo.
+    pub fn redo(&mut self) -> Option<Action> {
+        let turn = self.turns.get(self.position)?;
+        self.position += 1;
+        Some(turn.action)
+    }
+
+    /// Moves to the board after the given number of turns.
+    ///
+    /// # Returns
+    /// The board after `turn` turns, or `None` if that many turns have not been taken.
+    pub fn jump_to(&mut self, turn: usize) -> Option<Board> {
+        let board = *self.boards.get(turn)?;
+        self.position = turn;
+        Some(board)
+    }
+
+    /// The turns taken to reach the current board, in order.
+    #[must_use]
+    pub fn history(&self) -> &[RecordedTurn] {
+        &self.turns[..self.position]
+    }
+
+    /// The winner, if the game has ended as of the current turn.
+    #[must_use]
+    pub fn winner(&self) -> Option<Winner> {
+        match self.history().last()?.outcome {
+            Outcome::Ended(winner) => Some(winner),
+            Outcome::Continued | Outcome::Rejected(_) => None,
+        }
+    }
+
+    /// A record of the game up to the current turn.
+    #[must_use]
+    pub fn record(&self) -> GameRecord {
+        GameRecord {
+            start: GameStart::Position(self.boards[0], self.map.clone()),
+            turns: self.history().to_vec(),
+            end: Some(self.board()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{start_game_on_map, start_game_with_seed, RulesConfig};
+
+    fn game_with_turns(turns: usize) -> Game {
+        let mut game = Game::new(start_game_with_seed(3, 4).unwrap());
+        for _ in 0..turns {
+            let action = game.board().legal_actions()[0];
+            let _ = game.play(action).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn undo_and_redo_move_between_boards() {
+        let mut game = game_with_turns(3);
+        let last = game.board();
+        let action = game.history()[2].action;
+
+        assert_eq!(game.undo(), Some(action));
+        assert_eq!(game.history().len(), 2);
+        assert_ne!(game.board(), last);
+        assert_eq!(game.redo(), Some(action));
+        assert_eq!(game.board(), last);
+        assert_eq!(game.redo(), None);
+    }
+
+    #[test]
+    fn undo_stops_at_the_start() {
+        let mut game = game_with_turns(1);
+        assert!(game.undo().is_some());
+        assert_eq!(game.undo(), None);
+    }
+
+    #[test]
+    fn playing_after_undo_discards_redo() {
+        let mut game = game_with_turns(3);
+        let _ = game.undo();
+        let _ = game.undo();
+        let action = game.board().legal_actions()[1];
+        let _ = game.play(action).unwrap();
+        assert_eq!(game.history().len(), 2);
+        assert_eq!(game.redo(), None);
+    }
+
+    #[test]
+    fn failed_actions_are_not_kept() {
+        let mut game = game_with_turns(1);
+        let board = game.board();
+        assert!(game
+            .play(Action::EndNegotiation(crate::Crew::Rogues))
+            .is_err());
+        assert_eq!(game.board(), board);
+        assert_eq!(game.history().len(), 1);
+    }
+
+    #[test]
+    fn jump_to_moves_to_any_taken_turn() {
+        let mut game = game_with_turns(4);
+        let after_two = game.jump_to(2).unwrap();
+        assert_eq!(game.board(), after_two);
+        assert_eq!(game.history().len(), 2);
+        assert!(game.jump_to(4).is_some());
+        assert_eq!(game.jump_to(5), None);
+    }
+
+    #[test]
+    fn round_trips_through_a_record() {
+        let game = game_with_turns(4);
+        let rebuilt = Game::from_record(&game.record()).unwrap();
+        assert_eq!(rebuilt.board(), game.board());
+        assert_eq!(rebuilt.history(), game.history());
+    }
+
+    #[test]
+    fn round_trips_a_game_on_another_map_through_a_record() {
+        let map: Map = "keep home rogues : yard\nyard home goons : keep"
+            .parse()
+            .unwrap();
+        let board = start_game_on_map(2, 4, RulesConfig::STANDARD, &map).unwrap();
+        assert!(Game::on_map(start_game_with_seed(2, 4).unwrap(), map.clone()).is_none());
+        let mut game = Game::on_map(board, map.clone()).unwrap();
+        for _ in 0..3 {
+            let action = game.board().legal_actions()[0];
+            let _ = game.play(action).unwrap();
+        }
+
+        let record: GameRecord = game.record().to_string().parse().unwrap();
+        assert_eq!(*record.map(), map);
+        let rebuilt = Game::from_record(&record).unwrap();
+        assert_eq!(rebuilt.board(), game.board());
+        assert_eq!(rebuilt.history(), game.history());
+
+        let unnamed = Game::new(board).record();
+        assert!(unnamed
+            .to_string()
+            .starts_with("map zone0 home rogues : zone1\n"));
+        assert!(unnamed.to_string().parse::<GameRecord>().is_ok());
+    }
+}
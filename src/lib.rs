@@ -1,40 +1,88 @@
 //! An implementation of the game [Turncoats](https://mildamatildagames.wordpress.com/turncoats-2/)
 //! [bgg](https://boardgamegeek.com/boardgame/352238/turncoats).
 #![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
-#![warn(absolute_paths_not_starting_with_crate,elided_lifetimes_in_paths,explicit_outlives_requirements,keyword_idents,let_underscore_drop,macro_use_extern_crate,meta_variable_misuse,missing_abi,missing_copy_implementations,missing_docs,non_ascii_idents,noop_method_call,pointer_structural_match,rust_2021_incompatible_closure_captures,rust_2021_incompatible_or_patterns,rust_2021_prefixes_incompatible_syntax,rust_2021_prelude_collisions,single_use_lifetimes,trivial_casts,trivial_numeric_casts,unreachable_pub,unsafe_code,unsafe_op_in_unsafe_fn,unstable_features,unused_crate_dependencies,unused_extern_crates,unused_import_braces,unused_lifetimes,unused_macro_rules,unused_qualifications,unused_results,unused_tuple_struct_fields,variant_size_differences)]
+#![warn(
+    absolute_paths_not_starting_with_crate,
+    dead_code,
+    elided_lifetimes_in_paths,
+    explicit_outlives_requirements,
+    keyword_idents,
+    let_underscore_drop,
+    macro_use_extern_crate,
+    meta_variable_misuse,
+    missing_abi,
+    missing_copy_implementations,
+    missing_docs,
+    non_ascii_idents,
+    noop_method_call,
+    rust_2021_incompatible_closure_captures,
+    rust_2021_incompatible_or_patterns,
+    rust_2021_prefixes_incompatible_syntax,
+    rust_2021_prelude_collisions,
+    single_use_lifetimes,
+    trivial_casts,
+    trivial_numeric_casts,
+    unreachable_pub,
+    unsafe_code,
+    unsafe_op_in_unsafe_fn,
+    unstable_features,
+    unused_crate_dependencies,
+    unused_extern_crates,
+    unused_import_braces,
+    unused_lifetimes,
+    unused_macro_rules,
+    unused_qualifications,
+    unused_results,
+    variant_size_differences
+)]
 
 #[doc(inline)]
-pub use board::Board;
+pub use board::{
+    Board, Control, DecidedBy, HandWeights, Map, Placing, PlayerView, ScoreReport, SeparatedBy,
+    SpectatorView, Standing, ZoneControl, ZoneSpec,
+};
 use enum_iterator::Sequence;
+use rand::RngCore;
+#[cfg(all(test, not(feature = "serde")))]
+use serde_json as _;
 
+mod agent;
+mod beliefs;
 mod board;
-/// An error that can occur when performing an action.
-#[derive(Clone, Copy)]
-pub struct Error {
-    /// The action that caused the error.
-    pub action: Action,
-    /// The reason the action failed.
-    pub reason: &'static str,
-}
-
-impl Error {
-    const BAD_PLAYER_COUNT: &'static str = "The game supports between 2 and 5 players";
-    const CANNOT_MARCH_FROM_TO: &'static str = "Cannot march between non-adjacent zones";
-    const CANNOT_REMOVE_FROM_ATTACKING_FACTION: &'static str =
-        "Cannot remove crew member from the attacking crew";
-    const MUST_REMOVE_WHEN_ATTACKING: &'static str =
-        "Must remove at least one crew member when attacking";
-    const NEGOTIATION_IN_PROGRESS: &'static str = "Negotiation in progress";
-    const NOT_ENOUGH_STONES_IN_ZONE: &str = "Not enough crew members in zone";
-}
+mod error;
+mod game;
+mod notation;
+mod record;
+mod rules;
+#[doc(inline)]
+pub use agent::{
+    take_agent_turn, Agent, GreedyAgent, GreedyStyle, IsmctsAgent, RandomAgent, SearchLimits,
+};
+#[doc(inline)]
+pub use beliefs::{BeliefTracker, HandBelief};
+pub use error::{
+    Error, ErrorKind, InvariantError, MapError, NotationError, ReplayError, SetupError,
+};
+#[doc(inline)]
+pub use game::Game;
+#[doc(inline)]
+pub use record::{GameRecord, GameStart, Outcome, RecordedTurn};
+#[doc(inline)]
+pub use rules::{EmptyBagRule, GameEnd, RulesConfig, TiebreakOrder, UnusedSeats};
 
 /// The actions available each turn.
 ///
 /// # Errors
+/// Attempting any action once the game is over will return an error.
 /// Attempting any action other than `EndNegotiation` when a negotiation is in progress will return an error.
 /// Any action other than `StartNegotiation` requires the player to have at least one matching crew member in their hand.
 /// See each action for the specific errors that can occur.
-#[derive(Clone, Copy)]
+///
+/// # Notation
+/// Actions can be written as short text with [`Display`](std::fmt::Display) and read back with [`FromStr`](std::str::FromStr),
+/// for example `R rogues orange`, `M goons cyan->yellow 3`, `B bullies purple 1/0/2`, `N` and `E goons`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Action {
     /// Put a crew member on the board.
     /// The player places one crew member from their hand into a zone.
@@ -90,50 +138,117 @@ pub enum Action {
     /// Returns an error if this action is not preceded by `StartNegotiation`.
     EndNegotiation(Crew),
 }
-/// The zones on the board.
+/// A zone on the board, identified by its index in the [`Map`].
+///
+/// The zones of the standard map are associated constants, such as [`Zone::Red`]:
 #[doc = include_str!("../docs/layout.md")]
-#[allow(missing_docs)]
-#[derive(Clone, Copy, PartialEq, Eq, Sequence)]
-pub enum Zone {
+///
+/// Zones are written by their standard names, such as `red`,
+/// and zones past the standard eleven by their index.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Zone(u8);
+
+#[allow(non_upper_case_globals, missing_docs)]
+impl Zone {
     /// This zone starts with two `Crew::Rogues` crew members.
-    Red,
+    pub const Red: Self = Self(0);
     /// This zone starts with two `Crew::Bullies` crew members.
-    Blue,
+    pub const Blue: Self = Self(1);
     /// This zone starts with two `Crew::Goons` crew members.
-    Green,
-    Orange,
-    Yellow,
-    Cyan,
-    Magenta,
-    Purple,
-    White,
-    Black,
-    Gray,
+    pub const Green: Self = Self(2);
+    pub const Orange: Self = Self(3);
+    pub const Yellow: Self = Self(4);
+    pub const Cyan: Self = Self(5);
+    pub const Magenta: Self = Self(6);
+    pub const Purple: Self = Self(7);
+    pub const White: Self = Self(8);
+    pub const Black: Self = Self(9);
+    pub const Gray: Self = Self(10);
+
+    /// The zones of the standard map, in order.
+    pub const STANDARD: [Self; 11] = [
+        Self::Red,
+        Self::Blue,
+        Self::Green,
+        Self::Orange,
+        Self::Yellow,
+        Self::Cyan,
+        Self::Magenta,
+        Self::Purple,
+        Self::White,
+        Self::Black,
+        Self::Gray,
+    ];
+
+    /// Returns the zone at the given index of a map.
+    #[must_use]
+    pub const fn new(index: u8) -> Self {
+        Self(index)
+    }
+
+    /// Returns the index of the zone in its map.
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
 }
 
 /// The three types of crew members.
 #[allow(missing_docs)]
-#[derive(Clone, Copy, PartialEq, Eq, Sequence)]
-#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq, Sequence, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Crew {
     Rogues,
     Bullies,
     Goons,
 }
 
+/// The number of members of each crew in a zone, a hand, a tally or the bag.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CrewCounts {
+    /// The number of `Crew::Rogues` crew members.
+    pub rogues: u8,
+    /// The number of `Crew::Bullies` crew members.
+    pub bullies: u8,
+    /// The number of `Crew::Goons` crew members.
+    pub goons: u8,
+}
+
+impl CrewCounts {
+    /// Returns the number of members of the given crew.
+    #[must_use]
+    pub const fn get(self, crew: Crew) -> u8 {
+        match crew {
+            Crew::Rogues => self.rogues,
+            Crew::Bullies => self.bullies,
+            Crew::Goons => self.goons,
+        }
+    }
+
+    /// Returns the number of crew members of every crew together.
+    #[must_use]
+    pub const fn total(self) -> u16 {
+        self.rogues as u16 + self.bullies as u16 + self.goons as u16
+    }
+}
+
 /// The result of a turn.
 ///
 /// * `Board` - The board after the turn. In the case of an error, this will be the same as the board before the turn.
+///   Every action is validated in full before anything is changed.
 /// * `Result<Option<Winner>, Error>` - If the game is over, this will include the Winner. In the case of an error, this will include the error
-/// see [`Action`] for the possible errors.
+///   see [`Action`] for the possible errors.
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TurnResult(pub Board, pub Result<Option<Winner>, Error>);
 
 /// The players in the game.
 /// Unused players are skipped over.
 #[allow(missing_docs)]
-#[derive(Clone, Copy, PartialEq, Eq, Sequence, Default)]
-#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq, Sequence, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Player {
     #[default]
     Alpha,
@@ -144,7 +259,8 @@ pub enum Player {
 }
 
 /// The winner of the game.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Winner {
     /// This is the player that won
     Player(Player),
@@ -155,17 +271,108 @@ pub enum Winner {
 /// Starts a new game with the given number of players.
 /// Returns an error if the number of players is not between 2 and 5.
 ///
+/// The game is played by [`RulesConfig::STANDARD`]; see [`start_game_with_rules`] for house rules.
+///
+/// # Arguments
+/// * `num_players` - The number of players in the game.
+///
+/// # Returns
+///
+/// * `Ok(Board)` - The board for the game.
+///
+/// # Errors
+/// Returns [`SetupError::BadPlayerCount`] if the number of players is not between 2 and 5.
+pub fn start_game(num_players: u8) -> Result<Board, SetupError> {
+    start_game_with_seed(num_players, rand::random())
+}
+
+/// Starts a new game with the given number of players and a fixed seed.
+///
+/// The seed drives both the initial shuffle and every draw from the bag,
+/// so the same seed and the same sequence of [`Action`]s always produce the same game.
+///
+/// # Arguments
+/// * `num_players` - The number of players in the game.
+/// * `seed` - The seed for the board's random number generator.
+///
+/// # Returns
+///
+/// * `Ok(Board)` - The board for the game.
+///
+/// # Errors
+/// Returns [`SetupError::BadPlayerCount`] if the number of players is not between 2 and 5.
+pub fn start_game_with_seed(num_players: u8, seed: u64) -> Result<Board, SetupError> {
+    Board::build(num_players, seed)
+}
+
+/// Starts a new game with the given number of players, a fixed seed and house rules.
+///
+/// # Arguments
+/// * `num_players` - The number of players in the game.
+/// * `seed` - The seed for the board's random number generator.
+/// * `rules` - The rules to play with.
+///
+/// # Returns
+///
+/// * `Ok(Board)` - The board for the game.
+///
+/// # Errors
+/// Returns [`SetupError::BadPlayerCount`] if the number of players is not between 2 and 5,
+/// [`SetupError::NotEnoughCrew`] if the rules deal out more crew members than the bag holds,
+/// and [`SetupError::NoNegotiationsToEnd`] if the rules end the game before it begins.
+pub fn start_game_with_rules(
+    num_players: u8,
+    seed: u64,
+    rules: RulesConfig,
+) -> Result<Board, SetupError> {
+    Board::build_with_rules(num_players, seed, rules)
+}
+
+/// Starts a new game with the given number of players, a fixed seed and house rules
+/// on a map other than the standard one.
+///
+/// # Arguments
+/// * `num_players` - The number of players in the game.
+/// * `seed` - The seed for the board's random number generator.
+/// * `rules` - The rules to play with.
+/// * `map` - The map to play on.
+///
+/// # Returns
+///
+/// * `Ok(Board)` - The board for the game.
+///
+/// # Errors
+/// Returns the same errors as [`start_game_with_rules`], where the crew members needed
+/// depend on how many zones of the map are not home bases.
+pub fn start_game_on_map(
+    num_players: u8,
+    seed: u64,
+    rules: RulesConfig,
+    map: &Map,
+) -> Result<Board, SetupError> {
+    Board::build_on_map(num_players, seed, rules, map)
+}
+
+/// Starts a new game with the given number of players, seeded from the given random number generator.
+///
+/// The board keeps its own generator so that it can stay `Copy`;
+/// one `u64` is taken from `rng` to seed it.
+///
 /// # Arguments
 /// * `num_players` - The number of players in the game.
+/// * `rng` - The random number generator to seed the board from.
 ///
 /// # Returns
 ///
 /// * `Ok(Board)` - The board for the game.
 ///
 /// # Errors
-/// Returns `Err(&'static str)` if the number of players is not between 2 and 5.
-pub fn start_game(num_players: u8) -> Result<Board, &'static str> {
-    board::Board::build(num_players)
+/// Returns [`SetupError::BadPlayerCount`] if the number of players is not between 2 and 5.
+pub fn start_game_with_rng<R: RngCore + ?Sized>(
+    num_players: u8,
+    rng: &mut R,
+) -> Result<Board, SetupError> {
+    start_game_with_seed(num_players, rng.next_u64())
 }
 
 /// Takes the next turn
@@ -0,0 +1,404 @@
+//! Text forms of [`Action`], [`Zone`] and [`Crew`].
+//!
+//! | Action                 | Notation                  |
+//! |------------------------|---------------------------|
+//! | `Recruit`              | `R rogues orange`         |
+//! | `March`                | `M goons cyan->yellow 3`  |
+//! | `Battle`               | `B bullies purple 1/0/2`  |
+//! | `StartNegotiation`     | `N`                       |
+//! | `EndNegotiation`       | `E goons`                 |
+//!
+//! The amounts of a `Battle` are the rogues, bullies and goons to remove, in that order.
+//! Letters and names are read without regard to case.
+//!
+//! Zones past the standard eleven are written by their index, as in `R rogues 12`.
+//! The [`Display`](fmt::Display) and [`FromStr`] forms always use the standard names;
+//! [`Map::display_action`] and [`Map::parse_action`] use the names of a map,
+//! and [`Map::display_error`] names them when describing an [`ErrorKind`].
+//!
+//! Players are written as `a` for [`Player::Alpha`] through `e` for [`Player::Epsilon`].
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Action, Crew, CrewCounts, ErrorKind, Map, NotationError, Player, Zone};
+
+/// The names of [`Zone::STANDARD`], in order.
+const STANDARD_NAMES: [&str; 11] = [
+    "red", "blue", "green", "orange", "yellow", "cyan", "magenta", "purple", "white", "black",
+    "gray",
+];
+
+impl fmt::Display for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match STANDARD_NAMES.get(self.index()) {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{}", self.index()),
+        }
+    }
+}
+
+impl FromStr for Zone {
+    type Err = NotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::STANDARD
+            .into_iter()
+            .find(|zone| zone.to_string().eq_ignore_ascii_case(s))
+            .or_else(|| s.parse().ok().map(Self::new))
+            .ok_or_else(|| NotationError::invalid("zone", s))
+    }
+}
+
+/// How zones are named in text: by their standard names, or by those of a map.
+#[allow(clippy::redundant_pub_crate)] // `pub` would trip `unreachable_pub`
+#[derive(Clone, Copy)]
+pub(crate) enum ZoneNames<'a> {
+    Standard,
+    Map(&'a Map),
+}
+
+impl<'a> ZoneNames<'a> {
+    /// The zone, written by name, or by index if it has none.
+    pub(crate) const fn zone(self, zone: Zone) -> Named<'a, Zone> {
+        Named {
+            value: zone,
+            names: self,
+        }
+    }
+
+    /// Reads a zone by name, or by index.
+    pub(crate) fn parse(self, s: &str) -> Result<Zone, NotationError> {
+        match self {
+            Self::Standard => s.parse(),
+            Self::Map(map) => map
+                .zone_named(s)
+                .or_else(|| s.parse().ok().map(Zone::new))
+                .ok_or_else(|| NotationError::invalid("zone", s)),
+        }
+    }
+}
+
+/// A value written with the zone names it is given.
+#[allow(clippy::redundant_pub_crate)]
+#[derive(Clone, Copy)]
+pub(crate) struct Named<'a, T> {
+    pub(crate) value: T,
+    pub(crate) names: ZoneNames<'a>,
+}
+
+impl fmt::Display for Named<'_, Zone> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.names {
+            ZoneNames::Standard => self.value.fmt(f),
+            ZoneNames::Map(map) => match map.name(self.value) {
+                Some(name) => f.write_str(name),
+                None => write!(f, "{}", self.value.index()),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Crew {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Rogues => "rogues",
+            Self::Bullies => "bullies",
+            Self::Goons => "goons",
+        })
+    }
+}
+
+impl FromStr for Crew {
+    type Err = NotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        enum_iterator::all::<Self>()
+            .find(|crew| crew.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| NotationError::invalid("crew", s))
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Named {
+            value: *self,
+            names: ZoneNames::Standard,
+        }
+        .fmt(f)
+    }
+}
+
+impl fmt::Display for Named<'_, Action> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let zone = |zone| self.names.zone(zone);
+        match self.value {
+            Action::Recruit(crew, to) => write!(f, "R {crew} {}", zone(to)),
+            Action::March(crew, from, to, amount) => {
+                write!(f, "M {crew} {}->{} {amount}", zone(from), zone(to))
+            }
+            Action::Battle(crew, at, rogues, bullies, goons) => {
+                write!(f, "B {crew} {} {rogues}/{bullies}/{goons}", zone(at))
+            }
+            Action::StartNegotiation => f.write_str("N"),
+            Action::EndNegotiation(crew) => write!(f, "E {crew}"),
+        }
+    }
+}
+
+impl FromStr for Action {
+    type Err = NotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, ZoneNames::Standard)
+    }
+}
+
+impl Action {
+    /// Reads an action, naming zones as given.
+    pub(crate) fn parse_with(s: &str, names: ZoneNames<'_>) -> Result<Self, NotationError> {
+        let fields: Vec<&str> = s.split_whitespace().collect();
+        let Some((&letter, arguments)) = fields.split_first() else {
+            return Err(NotationError::invalid("action", s));
+        };
+
+        match (letter.to_ascii_uppercase().as_str(), arguments) {
+            ("R", &[crew, zone]) => Ok(Self::Recruit(crew.parse()?, names.parse(zone)?)),
+            ("M", &[crew, zones, amount]) => {
+                let (from, to) = zones
+                    .split_once("->")
+                    .ok_or_else(|| NotationError::invalid("zones", zones))?;
+                Ok(Self::March(
+                    crew.parse()?,
+                    names.parse(from)?,
+                    names.parse(to)?,
+                    NotationError::parse_number("amount", amount)?,
+                ))
+            }
+            ("B", &[crew, zone, amounts]) => {
+                let parsed = amounts
+                    .split('/')
+                    .map(|amount| NotationError::parse_number("amount", amount))
+                    .collect::<Result<Vec<u8>, _>>()?;
+                let &[rogues, bullies, goons] = parsed.as_slice() else {
+                    return Err(NotationError::invalid("amounts", amounts));
+                };
+                Ok(Self::Battle(
+                    crew.parse()?,
+                    names.parse(zone)?,
+                    rogues,
+                    bullies,
+                    goons,
+                ))
+            }
+            ("N", &[]) => Ok(Self::StartNegotiation),
+            ("E", &[crew]) => Ok(Self::EndNegotiation(crew.parse()?)),
+            (upper, _) => {
+                let expected = match upper {
+                    "R" => 3,
+                    "M" | "B" => 4,
+                    "N" => 1,
+                    "E" => 2,
+                    _ => return Err(NotationError::invalid("action", letter)),
+                };
+                Err(NotationError::FieldCount {
+                    expected,
+                    found: fields.len(),
+                })
+            }
+        }
+    }
+}
+
+/// Text forms that name zones as the map does.
+impl Map {
+    /// Writes an action in notation, naming zones as the map does.
+    #[must_use]
+    pub fn display_action(&self, action: Action) -> impl fmt::Display + '_ {
+        Named {
+            value: action,
+            names: ZoneNames::Map(self),
+        }
+    }
+
+    /// Reads an action in notation, naming zones as the map does.
+    /// Zones may also be given by index.
+    ///
+    /// # Errors
+    /// Returns a [`NotationError`] if the text is not an action on the map's zones.
+    pub fn parse_action(&self, s: &str) -> Result<Action, NotationError> {
+        Action::parse_with(s, ZoneNames::Map(self))
+    }
+
+    /// Describes why an action failed, naming zones as the map does.
+    #[must_use]
+    pub fn display_error(&self, kind: ErrorKind) -> impl fmt::Display + '_ {
+        Named {
+            value: kind,
+            names: ZoneNames::Map(self),
+        }
+    }
+}
+
+/// Helpers for reading the fields of every text form.
+impl NotationError {
+    /// The error for a field whose text could not be read.
+    pub(crate) fn invalid(field: &'static str, value: &str) -> Self {
+        Self::InvalidField {
+            field,
+            value: value.to_owned(),
+        }
+    }
+
+    /// Reads a number for the named field.
+    pub(crate) fn parse_number<T: FromStr>(field: &'static str, value: &str) -> Result<T, Self> {
+        value.parse().map_err(|_| Self::invalid(field, value))
+    }
+}
+
+impl CrewCounts {
+    /// Reads crew members written as `rogues.bullies.goons` for the named field.
+    pub(crate) fn parse_notation(field: &'static str, value: &str) -> Result<Self, NotationError> {
+        let counts = value
+            .split('.')
+            .map(|count| count.parse::<u8>().ok())
+            .collect::<Option<Vec<u8>>>();
+        match counts.as_deref() {
+            Some(&[rogues, bullies, goons]) => Ok(Self {
+                rogues,
+                bullies,
+                goons,
+            }),
+            _ => Err(NotationError::invalid(field, value)),
+        }
+    }
+}
+
+impl Player {
+    pub(crate) const fn letter(self) -> char {
+        match self {
+            Self::Alpha => 'a',
+            Self::Beta => 'b',
+            Self::Gamma => 'c',
+            Self::Delta => 'd',
+            Self::Epsilon => 'e',
+        }
+    }
+
+    pub(crate) fn from_letter(value: &str) -> Option<Self> {
+        enum_iterator::all::<Self>().find(|&player| {
+            value
+                .chars()
+                .map(|letter| letter.to_ascii_lowercase())
+                .eq([player.letter()])
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn writes_every_variant() {
+        assert_eq!(
+            Action::Recruit(Crew::Rogues, Zone::Orange).to_string(),
+            "R rogues orange"
+        );
+        assert_eq!(
+            Action::March(Crew::Goons, Zone::Cyan, Zone::Yellow, 3).to_string(),
+            "M goons cyan->yellow 3"
+        );
+        assert_eq!(
+            Action::Battle(Crew::Bullies, Zone::Purple, 1, 0, 2).to_string(),
+            "B bullies purple 1/0/2"
+        );
+        assert_eq!(Action::StartNegotiation.to_string(), "N");
+        assert_eq!(Action::EndNegotiation(Crew::Goons).to_string(), "E goons");
+    }
+
+    #[test]
+    fn round_trips_every_legal_action() {
+        let board = crate::start_game_with_seed(3, 5).unwrap();
+        for action in board.legal_actions() {
+            assert_eq!(action.to_string().parse::<Action>(), Ok(action));
+        }
+        for crew in enum_iterator::all::<Crew>() {
+            let action = Action::EndNegotiation(crew);
+            assert_eq!(action.to_string().parse::<Action>(), Ok(action));
+        }
+    }
+
+    #[test]
+    fn ignores_case_and_extra_space() {
+        assert_eq!(
+            "  m GOONS Cyan->yellow   3 ".parse::<Action>(),
+            Ok(Action::March(Crew::Goons, Zone::Cyan, Zone::Yellow, 3))
+        );
+    }
+
+    #[test]
+    fn names_zones_as_the_map_does() {
+        let map: Map = "keep : yard\nyard : keep".parse().unwrap();
+        let action = Action::March(Crew::Goons, Zone::new(0), Zone::new(1), 2);
+        assert_eq!(
+            map.display_action(action).to_string(),
+            "M goons keep->yard 2"
+        );
+        assert_eq!(map.parse_action("m goons KEEP->1 2"), Ok(action));
+        assert_eq!(action.to_string(), "M goons red->blue 2");
+        assert!(map.parse_action("M goons red->blue 2").is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_action() {
+        assert_eq!(
+            "X goons".parse::<Action>(),
+            Err(NotationError::InvalidField {
+                field: "action",
+                value: "X".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_number_of_arguments() {
+        assert_eq!(
+            "R goons".parse::<Action>(),
+            Err(NotationError::FieldCount {
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn writes_zones_past_the_standard_map_by_index() {
+        let action = Action::Recruit(Crew::Rogues, Zone::new(12));
+        assert_eq!(action.to_string(), "R rogues 12");
+        assert_eq!("R rogues 12".parse(), Ok(action));
+        assert_eq!("10".parse(), Ok(Zone::Gray));
+    }
+
+    #[test]
+    fn rejects_unknown_zone() {
+        assert_eq!(
+            "R goons teal".parse::<Action>(),
+            Err(NotationError::InvalidField {
+                field: "zone",
+                value: "teal".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_battle_amounts() {
+        assert_eq!(
+            "B goons red 1/2".parse::<Action>(),
+            Err(NotationError::InvalidField {
+                field: "amounts",
+                value: "1/2".to_owned()
+            })
+        );
+    }
+}
@@ -0,0 +1,638 @@
+//! Game records and replays.
+//!
+//! A record is written as text, one line per entry:
+//!
+//! ```text
+//! players 3
+//! seed 12345
+//! R rogues orange : ok
+//! R goons red : rejected missing-from-hand b goons
+//! N : ok
+//! E goons : winner b
+//! ```
+//!
+//! A game on a map other than the standard one starts with the map in [`Map`] form,
+//! each of its lines after `map`.
+//! The next two lines are either `players` and `seed`, for a game begun with
+//! [`start_game_on_map`], or `position` and `rng`,
+//! for a game begun from a position in [`Board::to_notation`] form.
+//! Either may be followed by a `rules` line in [`RulesConfig`] form,
+//! which is left out for a game played by the standard rules.
+//! Each remaining line is an action in [`Action`] notation and its [`Outcome`],
+//! with zones named as on the record's map:
+//! `ok`, `rejected` and the reason, `winner` and a player letter, or `draw`.
+//! The reason for a rejection is written in words joined by `-`, followed by
+//! what the reason names, as in `rejected missing-from-hand b goons`.
+//! A record may end with an `end` line holding the position after the last turn
+//! in [`Board::to_notation`] form, followed by an `rng` line with the state
+//! of its random number generator.
+//! Blank lines and lines starting with `#` are skipped.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::notation::{Named, ZoneNames};
+use crate::{
+    start_game_on_map, take_turn, Action, Board, Error, ErrorKind, Map, NotationError, Player,
+    ReplayError, RulesConfig, SetupError, TurnResult, Winner,
+};
+
+/// How a recorded game begins.
+#[allow(clippy::large_enum_variant, variant_size_differences)]
+#[derive(Clone)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameStart {
+    /// A new game, as started by [`start_game_on_map`].
+    Seed {
+        /// The number of players in the game.
+        num_players: u8,
+        /// The seed for the board's random number generator.
+        seed: u64,
+        /// The rules the game is played with.
+        rules: RulesConfig,
+        /// The map the game is played on.
+        map: Map,
+    },
+    /// A game that begins from a position, including the state of its random number generator
+    /// and the rules it is played with, on the map it is played on.
+    Position(Board, Map),
+}
+
+/// What happened when a recorded action was taken.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Outcome {
+    /// The action was taken and the game goes on.
+    Continued,
+    /// The action was rejected for the given reason and the board did not change.
+    Rejected(ErrorKind),
+    /// The action ended the game.
+    Ended(Winner),
+}
+
+impl From<&Result<Option<Winner>, Error>> for Outcome {
+    fn from(result: &Result<Option<Winner>, Error>) -> Self {
+        match result {
+            Ok(None) => Self::Continued,
+            Ok(Some(winner)) => Self::Ended(*winner),
+            Err(error) => Self::Rejected(error.kind),
+        }
+    }
+}
+
+/// An action in a game record and what happened when it was taken.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordedTurn {
+    /// The action that was taken.
+    pub action: Action,
+    /// What happened when it was taken.
+    pub outcome: Outcome,
+}
+
+/// The full history of a game: how it began and every action taken since.
+#[derive(Clone)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameRecord {
+    /// How the game began.
+    pub start: GameStart,
+    /// The actions taken, in order.
+    pub turns: Vec<RecordedTurn>,
+    /// The board after the last turn, if it was recorded.
+    pub end: Option<Board>,
+}
+
+impl GameRecord {
+    /// Creates an empty record for a game that begins with `start`.
+    #[must_use]
+    pub const fn new(start: GameStart) -> Self {
+        Self {
+            start,
+            turns: Vec::new(),
+            end: None,
+        }
+    }
+
+    /// Builds the board the recorded game begins with.
+    ///
+    /// # Errors
+    /// Returns a [`SetupError`] if the record starts a new game with a bad player count.
+    pub fn start_board(&self) -> Result<Board, SetupError> {
+        match &self.start {
+            GameStart::Seed {
+                num_players,
+                seed,
+                rules,
+                map,
+            } => start_game_on_map(*num_players, *seed, *rules, map),
+            GameStart::Position(board, _) => Ok(*board),
+        }
+    }
+
+    /// The map the recorded game is played on.
+    #[must_use]
+    pub const fn map(&self) -> &Map {
+        match &self.start {
+            GameStart::Seed { map, .. } | GameStart::Position(_, map) => map,
+        }
+    }
+
+    /// Takes a turn with [`take_turn`] and records the action, its outcome
+    /// and the board it leaves.
+    #[must_use]
+    pub fn play(&mut self, board: Board, action: Action) -> TurnResult {
+        let turn = take_turn(board, action);
+        self.turns.push(RecordedTurn {
+            action,
+            outcome: Outcome::from(&turn.1),
+        });
+        self.end = Some(turn.0);
+        turn
+    }
+
+    /// Steps a board through the record, checking every outcome.
+    ///
+    /// # Returns
+    /// The board before the first turn, followed by the board after each turn.
+    ///
+    /// # Errors
+    /// Returns a [`ReplayError`] if the game cannot be started,
+    /// if any turn has a different outcome than the one recorded, including
+    /// a rejection for a different reason, or if the last board is recorded
+    /// and the replay ends on a different board, down to its random number generator.
+    pub fn replay(&self) -> Result<Vec<Board>, ReplayError> {
+        let mut boards = vec![self.start_board().map_err(ReplayError::Setup)?];
+        for (turn, recorded) in self.turns.iter().enumerate() {
+            let board = boards[boards.len() - 1];
+            let TurnResult(next, result) = take_turn(board, recorded.action);
+            let found = Outcome::from(&result);
+            if found != recorded.outcome {
+                return Err(ReplayError::Mismatch {
+                    turn,
+                    expected: recorded.outcome,
+                    found,
+                });
+            }
+            boards.push(next);
+        }
+        if self.end.is_some_and(|end| end != boards[boards.len() - 1]) {
+            return Err(ReplayError::EndMismatch);
+        }
+        Ok(boards)
+    }
+
+    /// The winner of the recorded game, if it has ended.
+    #[must_use]
+    pub fn winner(&self) -> Option<Winner> {
+        self.turns.iter().find_map(|turn| match turn.outcome {
+            Outcome::Ended(winner) => Some(winner),
+            Outcome::Continued | Outcome::Rejected(_) => None,
+        })
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Named {
+            value: *self,
+            names: ZoneNames::Standard,
+        }
+        .fmt(f)
+    }
+}
+
+impl fmt::Display for Named<'_, Outcome> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value {
+            Outcome::Continued => f.write_str("ok"),
+            Outcome::Rejected(kind) => {
+                f.write_str("rejected ")?;
+                write_reason(f, kind, self.names)
+            }
+            Outcome::Ended(Winner::Player(player)) => write!(f, "winner {}", player.letter()),
+            Outcome::Ended(Winner::Draw) => f.write_str("draw"),
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = NotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, ZoneNames::Standard)
+    }
+}
+
+impl Outcome {
+    /// Reads an outcome, naming zones as given.
+    fn parse_with(s: &str, names: ZoneNames<'_>) -> Result<Self, NotationError> {
+        match s.split_whitespace().collect::<Vec<_>>().as_slice() {
+            ["ok"] => Ok(Self::Continued),
+            ["rejected", reason @ ..] => parse_reason(reason, names).map(Self::Rejected),
+            ["draw"] => Ok(Self::Ended(Winner::Draw)),
+            ["winner", player] => Player::from_letter(player)
+                .map(|player| Self::Ended(Winner::Player(player)))
+                .ok_or_else(|| NotationError::invalid("winner", player)),
+            _ => Err(NotationError::invalid("outcome", s)),
+        }
+    }
+}
+
+impl fmt::Display for GameRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if *self.map() != Map::standard() {
+            for line in self.map().to_string().lines() {
+                writeln!(f, "map {line}")?;
+            }
+        }
+        let rules = match self.start {
+            GameStart::Seed {
+                num_players,
+                seed,
+                rules,
+                ..
+            } => {
+                writeln!(f, "players {num_players}")?;
+                writeln!(f, "seed {seed}")?;
+                rules
+            }
+            GameStart::Position(board, _) => {
+                writeln!(f, "position {}", board.to_notation())?;
+                writeln!(f, "rng {}", board.rng_state())?;
+                board.rules()
+            }
+        };
+        if rules != RulesConfig::STANDARD {
+            writeln!(f, "rules {rules}")?;
+        }
+        let names = ZoneNames::Map(self.map());
+        for turn in &self.turns {
+            let action = Named {
+                value: turn.action,
+                names,
+            };
+            let outcome = Named {
+                value: turn.outcome,
+                names,
+            };
+            writeln!(f, "{action} : {outcome}")?;
+        }
+        if let Some(end) = self.end {
+            writeln!(f, "end {}", end.to_notation())?;
+            writeln!(f, "rng {}", end.rng_state())?;
+        }
+        Ok(())
+    }
+}
+
+/// The start of a recorded game, read before the rules a position is checked against.
+enum Start<'a> {
+    Seed(u8, u64),
+    Position(&'a str, u64),
+}
+
+impl FromStr for GameRecord {
+    type Err = NotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .peekable();
+
+        let mut map = String::new();
+        while let Some(line) = lines.next_if(|line| line.starts_with("map ")) {
+            map.push_str(&line["map ".len()..]);
+            map.push('\n');
+        }
+        let map = if map.is_empty() {
+            Map::standard()
+        } else {
+            map.parse().map_err(NotationError::InvalidMap)?
+        };
+
+        let start = if lines
+            .peek()
+            .is_some_and(|line| line.starts_with("position"))
+        {
+            let position = header(&mut lines, "position")?;
+            let state = header(&mut lines, "rng")?;
+            Start::Position(position, NotationError::parse_number("rng", state)?)
+        } else {
+            let num_players = header(&mut lines, "players")?;
+            let num_players = NotationError::parse_number("players", num_players)?;
+            let seed = header(&mut lines, "seed")?;
+            Start::Seed(num_players, NotationError::parse_number("seed", seed)?)
+        };
+
+        let rules = if lines.peek().is_some_and(|line| line.starts_with("rules")) {
+            header(&mut lines, "rules")?.parse()?
+        } else {
+            RulesConfig::STANDARD
+        };
+        let start = match start {
+            Start::Seed(num_players, seed) => GameStart::Seed {
+                num_players,
+                seed,
+                rules,
+                map: map.clone(),
+            },
+            Start::Position(position, state) => {
+                let mut board = Board::from_notation_with_rules(position, &map, rules)?;
+                board.set_rng_state(state);
+                GameStart::Position(board, map.clone())
+            }
+        };
+
+        let mut turns = Vec::new();
+        let mut end = None;
+        while let Some(line) = lines.next() {
+            if let Some(position) = line.strip_prefix("end ") {
+                let mut board = Board::from_notation_with_rules(position.trim(), &map, rules)?;
+                let state = header(&mut lines, "rng")?;
+                board.set_rng_state(NotationError::parse_number("rng", state)?);
+                end = Some(board);
+                if let Some(line) = lines.next() {
+                    return Err(NotationError::invalid("turn after end", line));
+                }
+                break;
+            }
+            let (action, outcome) = line
+                .rsplit_once(':')
+                .ok_or_else(|| NotationError::invalid("turn", line))?;
+            turns.push(RecordedTurn {
+                action: Action::parse_with(action, ZoneNames::Map(&map))?,
+                outcome: Outcome::parse_with(outcome, ZoneNames::Map(&map))?,
+            });
+        }
+
+        Ok(Self { start, turns, end })
+    }
+}
+
+/// Reads the value of the next line, which must start with `key`.
+fn header<'a>(
+    lines: &mut impl Iterator<Item = &'a str>,
+    key: &'static str,
+) -> Result<&'a str, NotationError> {
+    let line = lines.next().unwrap_or_default();
+    line.strip_prefix(key)
+        .filter(|value| value.starts_with(' '))
+        .map(str::trim)
+        .ok_or_else(|| NotationError::invalid(key, line))
+}
+
+/// Writes the reason for a rejection as in the text form of a record.
+fn write_reason(f: &mut fmt::Formatter<'_>, kind: ErrorKind, names: ZoneNames<'_>) -> fmt::Result {
+    let zone = |zone| names.zone(zone);
+    match kind {
+        ErrorKind::NotAdjacent { from, to } => {
+            write!(f, "not-adjacent {} {}", zone(from), zone(to))
+        }
+        ErrorKind::RemovingAttackingCrew(crew) => write!(f, "removing-attacking-crew {crew}"),
+        ErrorKind::NothingRemoved => f.write_str("nothing-removed"),
+        ErrorKind::NotEnoughCrew {
+            zone: short,
+            crew,
+            needed,
+            available,
+        } => write!(
+            f,
+            "not-enough-crew {} {crew} {needed} {available}",
+            zone(short)
+        ),
+        ErrorKind::MissingFromHand { player, crew } => {
+            write!(f, "missing-from-hand {} {crew}", player.letter())
+        }
+        ErrorKind::NegotiationInProgress => f.write_str("negotiation-in-progress"),
+        ErrorKind::NoNegotiationInProgress => f.write_str("no-negotiation-in-progress"),
+        ErrorKind::UnknownZone(unknown) => write!(f, "unknown-zone {}", zone(unknown)),
+        ErrorKind::EmptyBag => f.write_str("empty-bag"),
+        ErrorKind::GameOver => f.write_str("game-over"),
+    }
+}
+
+/// Reads the reason for a rejection written by [`write_reason`], split into words.
+fn parse_reason(words: &[&str], names: ZoneNames<'_>) -> Result<ErrorKind, NotationError> {
+    Ok(match *words {
+        ["not-adjacent", from, to] => ErrorKind::NotAdjacent {
+            from: names.parse(from)?,
+            to: names.parse(to)?,
+        },
+        ["removing-attacking-crew", crew] => ErrorKind::RemovingAttackingCrew(crew.parse()?),
+        ["nothing-removed"] => ErrorKind::NothingRemoved,
+        ["not-enough-crew", zone, crew, needed, available] => ErrorKind::NotEnoughCrew {
+            zone: names.parse(zone)?,
+            crew: crew.parse()?,
+            needed: NotationError::parse_number("needed", needed)?,
+            available: NotationError::parse_number("available", available)?,
+        },
+        ["missing-from-hand", player, crew] => ErrorKind::MissingFromHand {
+            player: Player::from_letter(player)
+                .ok_or_else(|| NotationError::invalid("player", player))?,
+            crew: crew.parse()?,
+        },
+        ["negotiation-in-progress"] => ErrorKind::NegotiationInProgress,
+        ["no-negotiation-in-progress"] => ErrorKind::NoNegotiationInProgress,
+        ["unknown-zone", zone] => ErrorKind::UnknownZone(names.parse(zone)?),
+        ["empty-bag"] => ErrorKind::EmptyBag,
+        ["game-over"] => ErrorKind::GameOver,
+        _ => return Err(NotationError::invalid("reason", &words.join(" "))),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{start_game_with_seed, Crew, EmptyBagRule, UnusedSeats, Zone};
+
+    fn recorded_game() -> GameRecord {
+        let mut record = GameRecord::new(GameStart::Seed {
+            num_players: 2,
+            seed: 11,
+            rules: RulesConfig::STANDARD,
+            map: Map::standard(),
+        });
+        let mut board = record.start_board().unwrap();
+        for _ in 0..2 {
+            TurnResult(board, _) = record.play(board, Action::StartNegotiation);
+            let action = board.legal_actions()[0];
+            TurnResult(board, _) = record.play(board, action);
+        }
+        let TurnResult(_, _) = record.play(board, Action::Recruit(Crew::Goons, Zone::Cyan));
+        record
+    }
+
+    #[test]
+    fn replays_a_recorded_game() {
+        let record = recorded_game();
+        let boards = record.replay().unwrap();
+        assert_eq!(boards.len(), record.turns.len() + 1);
+        assert!(record.winner().is_some());
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let record = recorded_game();
+        let text = record.to_string();
+        let read: GameRecord = text.parse().unwrap();
+        assert_eq!(read.turns, record.turns);
+        assert_eq!(read.to_string(), text);
+    }
+
+    #[test]
+    fn round_trips_a_position_start() {
+        let mut board = start_game_with_seed(3, 2).unwrap();
+        TurnResult(board, _) = take_turn(board, Action::StartNegotiation);
+        let mut record = GameRecord::new(GameStart::Position(board, Map::standard()));
+        let action = board.legal_actions()[0];
+        let TurnResult(_, _) = record.play(board, action);
+
+        let read: GameRecord = record.to_string().parse().unwrap();
+        assert_eq!(read.replay().unwrap(), record.replay().unwrap());
+    }
+
+    #[test]
+    fn round_trips_house_rules() {
+        let rules = RulesConfig {
+            empty_bag: EmptyBagRule::NoDraw,
+            unused_seats: UnusedSeats::Dealt,
+            ..RulesConfig::STANDARD
+        };
+        let record = GameRecord::new(GameStart::Seed {
+            num_players: 4,
+            seed: 6,
+            rules,
+            map: Map::standard(),
+        });
+        let text = record.to_string();
+        assert!(text.contains("empty-bag=no-draw"));
+        let read: GameRecord = text.parse().unwrap();
+        assert_eq!(read.start_board().unwrap().rules(), rules);
+
+        let record = GameRecord::new(GameStart::Position(
+            read.start_board().unwrap(),
+            Map::standard(),
+        ));
+        let read: GameRecord = record.to_string().parse().unwrap();
+        assert_eq!(read.start_board().unwrap().rules(), rules);
+    }
+
+    #[test]
+    fn round_trips_a_game_on_another_map() {
+        let map: Map = "keep home rogues : yard\nyard home goons : keep"
+            .parse()
+            .unwrap();
+        let mut record = GameRecord::new(GameStart::Seed {
+            num_players: 2,
+            seed: 4,
+            rules: RulesConfig::STANDARD,
+            map: map.clone(),
+        });
+        let mut board = record.start_board().unwrap();
+        for _ in 0..3 {
+            let action = board.legal_actions()[0];
+            TurnResult(board, _) = record.play(board, action);
+        }
+        let TurnResult(_, _) = record.play(
+            board,
+            Action::March(Crew::Rogues, Zone::new(0), Zone::new(1), 9),
+        );
+        let text = record.to_string();
+        assert!(text.starts_with("map keep home rogues : yard\nmap yard home goons : keep\n"));
+        assert!(text.contains("M rogues keep->yard 9 : rejected not-enough-crew keep rogues 9 5"));
+        let read: GameRecord = text.parse().unwrap();
+        assert_eq!(*read.map(), map);
+        assert_eq!(read.replay().unwrap(), record.replay().unwrap());
+
+        let record = GameRecord::new(GameStart::Position(board, map));
+        let read: GameRecord = record.to_string().parse().unwrap();
+        assert_eq!(read.start_board().unwrap(), board);
+    }
+
+    #[test]
+    fn reports_a_mismatched_outcome() {
+        let mut record = recorded_game();
+        record.turns[0].outcome = Outcome::Rejected(ErrorKind::EmptyBag);
+        assert_eq!(
+            record.replay(),
+            Err(ReplayError::Mismatch {
+                turn: 0,
+                expected: Outcome::Rejected(ErrorKind::EmptyBag),
+                found: Outcome::Continued,
+            })
+        );
+    }
+
+    #[test]
+    fn reports_a_rejection_for_a_different_reason() {
+        let board = start_game_with_seed(2, 11).unwrap();
+        let mut record = GameRecord::new(GameStart::Position(board, Map::standard()));
+        let action = Action::EndNegotiation(Crew::Rogues);
+        let TurnResult(_, result) = record.play(board, action);
+        assert_eq!(
+            record.turns[0].outcome,
+            Outcome::Rejected(ErrorKind::NoNegotiationInProgress)
+        );
+        assert_eq!(result.unwrap_err().kind, ErrorKind::NoNegotiationInProgress);
+
+        record.turns[0].outcome = Outcome::Rejected(ErrorKind::NegotiationInProgress);
+        assert!(matches!(
+            record.replay(),
+            Err(ReplayError::Mismatch { turn: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn reports_a_different_end_position() {
+        let mut record = recorded_game();
+        assert!(record.replay().is_ok());
+        record.end = Some(start_game_with_seed(2, 12).unwrap());
+        assert_eq!(record.replay(), Err(ReplayError::EndMismatch));
+
+        let mut record = recorded_game();
+        let mut text = record.to_string();
+        let rng = text.rfind("rng ").unwrap();
+        text.replace_range(rng.., "rng 1\n");
+        let read: GameRecord = text.parse().unwrap();
+        assert_eq!(read.replay(), Err(ReplayError::EndMismatch));
+
+        record.end.as_mut().unwrap().set_rng_state(1);
+        assert_eq!(record.replay(), Err(ReplayError::EndMismatch));
+    }
+
+    #[test]
+    fn rejection_reasons_round_trip_through_text() {
+        let reasons = [
+            ErrorKind::NotAdjacent {
+                from: Zone::Red,
+                to: Zone::Gray,
+            },
+            ErrorKind::RemovingAttackingCrew(Crew::Goons),
+            ErrorKind::NothingRemoved,
+            ErrorKind::NotEnoughCrew {
+                zone: Zone::Cyan,
+                crew: Crew::Bullies,
+                needed: 3,
+                available: 1,
+            },
+            ErrorKind::MissingFromHand {
+                player: Player::Delta,
+                crew: Crew::Rogues,
+            },
+            ErrorKind::NegotiationInProgress,
+            ErrorKind::NoNegotiationInProgress,
+            ErrorKind::UnknownZone(Zone::new(20)),
+            ErrorKind::EmptyBag,
+            ErrorKind::GameOver,
+        ];
+        for reason in reasons {
+            let outcome = Outcome::Rejected(reason);
+            assert_eq!(outcome.to_string().parse(), Ok(outcome));
+        }
+        assert!("rejected for no reason".parse::<Outcome>().is_err());
+    }
+}
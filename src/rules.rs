@@ -0,0 +1,279 @@
+//! Rules that vary between groups of players.
+//!
+//! The rules are written as text as `key=value` pairs separated by spaces,
+//! with any rule that is left out taking its standard value:
+//!
+//! ```text
+//! hand-size=6 game-end=3 tiebreak=flags-swords empty-bag=no-draw
+//! ```
+//!
+//! `game-end` is either `every-player` or a number of negotiations in a row,
+//! and `bag` is the number of each crew in the bag as `rogues.bullies.goons`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{CrewCounts, NotationError};
+
+/// What `StartNegotiation` does when the bag is empty.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EmptyBagRule {
+    /// The negotiation is rejected with [`ErrorKind::EmptyBag`](crate::ErrorKind::EmptyBag).
+    #[default]
+    Reject,
+    /// The negotiation goes ahead without a draw,
+    /// as long as the player holds a crew member to end it with.
+    NoDraw,
+}
+
+/// How many negotiations in a row end the game.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameEnd {
+    /// As many negotiations in a row as there are players.
+    #[default]
+    EveryPlayer,
+    /// A fixed number of negotiations in a row, whatever the number of players.
+    Negotiations(u8),
+}
+
+/// The order in which the tallies break a tie for control.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TiebreakOrder {
+    /// The swords tally first, then the flags tally.
+    #[default]
+    SwordsThenFlags,
+    /// The flags tally first, then the swords tally.
+    FlagsThenSwords,
+}
+
+/// What happens to the seats no player sits in.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UnusedSeats {
+    /// Unused seats do not exist, and every crew member not dealt stays in the bag.
+    #[default]
+    Empty,
+    /// Unused seats are dealt a hand as if a player sat there,
+    /// which stays out of play for the whole game.
+    Dealt,
+}
+
+/// The rules a game is played with.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RulesConfig {
+    /// The number of crew members dealt to each hand.
+    pub hand_size: u8,
+    /// The number of crew members placed in each zone that is not a home base.
+    pub zone_fill: u8,
+    /// How many negotiations in a row end the game.
+    pub game_end: GameEnd,
+    /// The order in which the tallies break a tie for control.
+    pub tiebreak: TiebreakOrder,
+    /// What happens to the seats no player sits in.
+    pub unused_seats: UnusedSeats,
+    /// What `StartNegotiation` does when the bag is empty.
+    pub empty_bag: EmptyBagRule,
+    /// The crew members in the bag before the zones and hands are dealt from it.
+    /// The home bases are not dealt from the bag.
+    ///
+    /// Rules saved before the bag could be chosen use the standard bag.
+    #[cfg_attr(feature = "serde", serde(default = "standard_bag"))]
+    pub bag: CrewCounts,
+}
+
+impl RulesConfig {
+    /// The rules as written in the rulebook.
+    pub const STANDARD: Self = Self {
+        hand_size: 8,
+        zone_fill: 2,
+        game_end: GameEnd::EveryPlayer,
+        tiebreak: TiebreakOrder::SwordsThenFlags,
+        unused_seats: UnusedSeats::Empty,
+        empty_bag: EmptyBagRule::Reject,
+        bag: CrewCounts {
+            rogues: 19,
+            bullies: 19,
+            goons: 19,
+        },
+    };
+
+    /// Returns the number of negotiations in a row that ends a game with `num_players`.
+    #[must_use]
+    pub const fn negotiations_to_end(self, num_players: u8) -> u8 {
+        match self.game_end {
+            GameEnd::EveryPlayer => num_players,
+            GameEnd::Negotiations(count) => count,
+        }
+    }
+}
+
+impl Default for RulesConfig {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+/// The bag of [`RulesConfig::STANDARD`], for rules saved without one.
+#[cfg(feature = "serde")]
+const fn standard_bag() -> CrewCounts {
+    RulesConfig::STANDARD.bag
+}
+
+impl fmt::Display for RulesConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "hand-size={} zone-fill={} game-end=",
+            self.hand_size, self.zone_fill
+        )?;
+        match self.game_end {
+            GameEnd::EveryPlayer => f.write_str("every-player")?,
+            GameEnd::Negotiations(count) => write!(f, "{count}")?,
+        }
+        let tiebreak = match self.tiebreak {
+            TiebreakOrder::SwordsThenFlags => "swords-flags",
+            TiebreakOrder::FlagsThenSwords => "flags-swords",
+        };
+        let unused_seats = match self.unused_seats {
+            UnusedSeats::Empty => "empty",
+            UnusedSeats::Dealt => "dealt",
+        };
+        let empty_bag = match self.empty_bag {
+            EmptyBagRule::Reject => "reject",
+            EmptyBagRule::NoDraw => "no-draw",
+        };
+        write!(
+            f,
+            " tiebreak={tiebreak} unused-seats={unused_seats} empty-bag={empty_bag} bag={}.{}.{}",
+            self.bag.rogues, self.bag.bullies, self.bag.goons
+        )
+    }
+}
+
+impl FromStr for RulesConfig {
+    type Err = NotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rules = Self::STANDARD;
+        for pair in s.split_whitespace() {
+            let Some((key, value)) = pair.split_once('=') else {
+                return Err(NotationError::invalid("rule", pair));
+            };
+            match (key, value) {
+                ("hand-size", count) => {
+                    rules.hand_size = NotationError::parse_number("hand-size", count)?;
+                }
+                ("zone-fill", count) => {
+                    rules.zone_fill = NotationError::parse_number("zone-fill", count)?;
+                }
+                ("game-end", "every-player") => rules.game_end = GameEnd::EveryPlayer,
+                ("game-end", count) => {
+                    rules.game_end =
+                        GameEnd::Negotiations(NotationError::parse_number("game-end", count)?);
+                }
+                ("tiebreak", "swords-flags") => rules.tiebreak = TiebreakOrder::SwordsThenFlags,
+                ("tiebreak", "flags-swords") => rules.tiebreak = TiebreakOrder::FlagsThenSwords,
+                ("unused-seats", "empty") => rules.unused_seats = UnusedSeats::Empty,
+                ("unused-seats", "dealt") => rules.unused_seats = UnusedSeats::Dealt,
+                ("empty-bag", "reject") => rules.empty_bag = EmptyBagRule::Reject,
+                ("empty-bag", "no-draw") => rules.empty_bag = EmptyBagRule::NoDraw,
+                ("bag", counts) => rules.bag = CrewCounts::parse_notation("bag", counts)?,
+                ("tiebreak", _) => return Err(NotationError::invalid("tiebreak", value)),
+                ("unused-seats", _) => return Err(NotationError::invalid("unused-seats", value)),
+                ("empty-bag", _) => return Err(NotationError::invalid("empty-bag", value)),
+                _ => return Err(NotationError::invalid("rule", pair)),
+            }
+        }
+        Ok(rules)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_text() {
+        let rules = RulesConfig {
+            hand_size: 6,
+            zone_fill: 1,
+            game_end: GameEnd::Negotiations(4),
+            tiebreak: TiebreakOrder::FlagsThenSwords,
+            unused_seats: UnusedSeats::Dealt,
+            empty_bag: EmptyBagRule::NoDraw,
+            bag: CrewCounts {
+                rogues: 20,
+                bullies: 10,
+                goons: 5,
+            },
+        };
+        assert_eq!(
+            rules.to_string(),
+            "hand-size=6 zone-fill=1 game-end=4 tiebreak=flags-swords \
+             unused-seats=dealt empty-bag=no-draw bag=20.10.5"
+        );
+        assert_eq!(rules.to_string().parse(), Ok(rules));
+        assert_eq!(
+            RulesConfig::STANDARD.to_string().parse(),
+            Ok(RulesConfig::STANDARD)
+        );
+    }
+
+    #[test]
+    fn leaves_missing_rules_standard() {
+        assert_eq!("".parse(), Ok(RulesConfig::STANDARD));
+        assert_eq!(
+            "empty-bag=no-draw".parse(),
+            Ok(RulesConfig {
+                empty_bag: EmptyBagRule::NoDraw,
+                ..RulesConfig::STANDARD
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_rules() {
+        assert_eq!(
+            "hand=8".parse::<RulesConfig>(),
+            Err(NotationError::InvalidField {
+                field: "rule",
+                value: "hand=8".to_owned()
+            })
+        );
+        assert_eq!(
+            "empty-bag=draw".parse::<RulesConfig>(),
+            Err(NotationError::InvalidField {
+                field: "empty-bag",
+                value: "draw".to_owned()
+            })
+        );
+        assert_eq!(
+            "bag=19.19".parse::<RulesConfig>(),
+            Err(NotationError::InvalidField {
+                field: "bag",
+                value: "19.19".to_owned()
+            })
+        );
+        assert_eq!(
+            "game-end=never".parse::<RulesConfig>(),
+            Err(NotationError::InvalidField {
+                field: "game-end",
+                value: "never".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn ends_after_the_configured_negotiations() {
+        assert_eq!(RulesConfig::STANDARD.negotiations_to_end(4), 4);
+        let rules = RulesConfig {
+            game_end: GameEnd::Negotiations(2),
+            ..RulesConfig::STANDARD
+        };
+        assert_eq!(rules.negotiations_to_end(4), 2);
+    }
+}